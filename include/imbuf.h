@@ -0,0 +1,423 @@
+/* C interface of imbuf, which is implemented in src/ffi.rs (enable the `ffi` feature)
+ *
+ * Channels own their pixels. Every channel must be released exactly once,
+ * either with `imbuf_channel_<p>_drop` or by moving it into a dynamic image.
+ * All functions returning ImbufStatus only write to `out` on IMBUF_STATUS_OK.
+ */
+#ifndef IMBUF_H
+#define IMBUF_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum ImbufStatus {
+    IMBUF_STATUS_OK = 0,
+    IMBUF_STATUS_NULL_POINTER = 1,
+    /* Width, height or pixel elements are 0 or the stride is smaller than a row */
+    IMBUF_STATUS_INVALID_LAYOUT = 2,
+    IMBUF_STATUS_INDEX_OUT_OF_BOUNDS = 3,
+    /* The channel has a different primitive than requested */
+    IMBUF_STATUS_INCOMPATIBLE_PRIMITIVE = 4,
+    /* The channel was created by a build with a different ImbufChannelAbi */
+    IMBUF_STATUS_INCOMPATIBLE_ABI = 5,
+} ImbufStatus;
+
+typedef enum ImbufPrimitive {
+    IMBUF_PRIMITIVE_U8 = 0,
+    IMBUF_PRIMITIVE_I8 = 1,
+    IMBUF_PRIMITIVE_U16 = 2,
+    IMBUF_PRIMITIVE_I16 = 3,
+    IMBUF_PRIMITIVE_U32 = 4,
+    IMBUF_PRIMITIVE_I32 = 5,
+    IMBUF_PRIMITIVE_F32 = 6,
+    IMBUF_PRIMITIVE_F64 = 7,
+    IMBUF_PRIMITIVE_F16 = 8,
+    IMBUF_PRIMITIVE_BF16 = 9,
+} ImbufPrimitive;
+
+/* Incremented whenever the layout of the channels or vtables changes */
+#define IMBUF_CHANNEL_ABI_VERSION 1
+
+/* First field of every vtable, which is pointed to by the first field of every channel.
+ * This prefix stays the same in all versions, so channels of other builds can be checked with `imbuf_channel_<p>_import`.
+ * Channels of builds without ImbufChannelAbi (where `ptr` is the first field) cannot be detected and are misread.
+ */
+typedef struct ImbufChannelAbi {
+    uint32_t version;
+    uint32_t channel_size;
+    uint32_t channel_align;
+    uint32_t vtable_size;
+    uint32_t primitive_size;
+    uint32_t primitive_align;
+    /* Character of NumPy's type kind, e.g. 'f' for floats */
+    uint32_t primitive_kind;
+} ImbufChannelAbi;
+
+typedef struct ImbufChannelInfo {
+    ImbufPrimitive primitive;
+    uint32_t width;
+    uint32_t height;
+    /* Distance between the start of two consecutive rows in primitives */
+    size_t stride;
+    uint8_t pixel_elements;
+} ImbufChannelInfo;
+
+/* Opaque image with at least one channel, whose channels may have different primitives */
+typedef struct ImbufDynamicImage ImbufDynamicImage;
+
+/* Called once the last channel referencing external memory is dropped */
+typedef void (*ImbufReleaseFn)(void *context);
+
+/* u8 */
+
+struct ImbufChannel_u8;
+
+typedef struct ImbufChannelVTable_u8 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_u8 (*clone)(const struct ImbufChannel_u8 *channel);
+    void (*make_mut)(struct ImbufChannel_u8 *channel);
+    void (*drop)(struct ImbufChannel_u8 *channel);
+} ImbufChannelVTable_u8;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_u8 {
+    const ImbufChannelVTable_u8 *vtable;
+    const uint8_t *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_u8;
+
+ImbufStatus imbuf_channel_u8_from_copy(const uint8_t *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_u8 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_u8_from_external(const uint8_t *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_u8 *out);
+ImbufStatus imbuf_channel_u8_clone(const ImbufChannel_u8 *channel, ImbufChannel_u8 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+uint8_t *imbuf_channel_u8_make_mut(ImbufChannel_u8 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_u8_drop(ImbufChannel_u8 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_u8_import(ImbufChannel_u8 *channel, ImbufChannel_u8 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_u8_import` */
+ImbufStatus imbuf_dynamic_image_new_u8(ImbufChannel_u8 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_u8(ImbufDynamicImage *image, ImbufChannel_u8 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_u8(const ImbufDynamicImage *image, size_t index, ImbufChannel_u8 *out);
+
+/* i8 */
+
+struct ImbufChannel_i8;
+
+typedef struct ImbufChannelVTable_i8 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_i8 (*clone)(const struct ImbufChannel_i8 *channel);
+    void (*make_mut)(struct ImbufChannel_i8 *channel);
+    void (*drop)(struct ImbufChannel_i8 *channel);
+} ImbufChannelVTable_i8;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_i8 {
+    const ImbufChannelVTable_i8 *vtable;
+    const int8_t *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_i8;
+
+ImbufStatus imbuf_channel_i8_from_copy(const int8_t *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_i8 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_i8_from_external(const int8_t *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_i8 *out);
+ImbufStatus imbuf_channel_i8_clone(const ImbufChannel_i8 *channel, ImbufChannel_i8 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+int8_t *imbuf_channel_i8_make_mut(ImbufChannel_i8 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_i8_drop(ImbufChannel_i8 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_i8_import(ImbufChannel_i8 *channel, ImbufChannel_i8 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_i8_import` */
+ImbufStatus imbuf_dynamic_image_new_i8(ImbufChannel_i8 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_i8(ImbufDynamicImage *image, ImbufChannel_i8 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_i8(const ImbufDynamicImage *image, size_t index, ImbufChannel_i8 *out);
+
+/* u16 */
+
+struct ImbufChannel_u16;
+
+typedef struct ImbufChannelVTable_u16 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_u16 (*clone)(const struct ImbufChannel_u16 *channel);
+    void (*make_mut)(struct ImbufChannel_u16 *channel);
+    void (*drop)(struct ImbufChannel_u16 *channel);
+} ImbufChannelVTable_u16;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_u16 {
+    const ImbufChannelVTable_u16 *vtable;
+    const uint16_t *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_u16;
+
+ImbufStatus imbuf_channel_u16_from_copy(const uint16_t *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_u16 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_u16_from_external(const uint16_t *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_u16 *out);
+ImbufStatus imbuf_channel_u16_clone(const ImbufChannel_u16 *channel, ImbufChannel_u16 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+uint16_t *imbuf_channel_u16_make_mut(ImbufChannel_u16 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_u16_drop(ImbufChannel_u16 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_u16_import(ImbufChannel_u16 *channel, ImbufChannel_u16 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_u16_import` */
+ImbufStatus imbuf_dynamic_image_new_u16(ImbufChannel_u16 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_u16(ImbufDynamicImage *image, ImbufChannel_u16 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_u16(const ImbufDynamicImage *image, size_t index, ImbufChannel_u16 *out);
+
+/* i16 */
+
+struct ImbufChannel_i16;
+
+typedef struct ImbufChannelVTable_i16 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_i16 (*clone)(const struct ImbufChannel_i16 *channel);
+    void (*make_mut)(struct ImbufChannel_i16 *channel);
+    void (*drop)(struct ImbufChannel_i16 *channel);
+} ImbufChannelVTable_i16;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_i16 {
+    const ImbufChannelVTable_i16 *vtable;
+    const int16_t *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_i16;
+
+ImbufStatus imbuf_channel_i16_from_copy(const int16_t *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_i16 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_i16_from_external(const int16_t *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_i16 *out);
+ImbufStatus imbuf_channel_i16_clone(const ImbufChannel_i16 *channel, ImbufChannel_i16 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+int16_t *imbuf_channel_i16_make_mut(ImbufChannel_i16 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_i16_drop(ImbufChannel_i16 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_i16_import(ImbufChannel_i16 *channel, ImbufChannel_i16 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_i16_import` */
+ImbufStatus imbuf_dynamic_image_new_i16(ImbufChannel_i16 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_i16(ImbufDynamicImage *image, ImbufChannel_i16 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_i16(const ImbufDynamicImage *image, size_t index, ImbufChannel_i16 *out);
+
+/* u32 */
+
+struct ImbufChannel_u32;
+
+typedef struct ImbufChannelVTable_u32 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_u32 (*clone)(const struct ImbufChannel_u32 *channel);
+    void (*make_mut)(struct ImbufChannel_u32 *channel);
+    void (*drop)(struct ImbufChannel_u32 *channel);
+} ImbufChannelVTable_u32;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_u32 {
+    const ImbufChannelVTable_u32 *vtable;
+    const uint32_t *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_u32;
+
+ImbufStatus imbuf_channel_u32_from_copy(const uint32_t *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_u32 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_u32_from_external(const uint32_t *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_u32 *out);
+ImbufStatus imbuf_channel_u32_clone(const ImbufChannel_u32 *channel, ImbufChannel_u32 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+uint32_t *imbuf_channel_u32_make_mut(ImbufChannel_u32 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_u32_drop(ImbufChannel_u32 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_u32_import(ImbufChannel_u32 *channel, ImbufChannel_u32 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_u32_import` */
+ImbufStatus imbuf_dynamic_image_new_u32(ImbufChannel_u32 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_u32(ImbufDynamicImage *image, ImbufChannel_u32 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_u32(const ImbufDynamicImage *image, size_t index, ImbufChannel_u32 *out);
+
+/* i32 */
+
+struct ImbufChannel_i32;
+
+typedef struct ImbufChannelVTable_i32 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_i32 (*clone)(const struct ImbufChannel_i32 *channel);
+    void (*make_mut)(struct ImbufChannel_i32 *channel);
+    void (*drop)(struct ImbufChannel_i32 *channel);
+} ImbufChannelVTable_i32;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_i32 {
+    const ImbufChannelVTable_i32 *vtable;
+    const int32_t *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_i32;
+
+ImbufStatus imbuf_channel_i32_from_copy(const int32_t *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_i32 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_i32_from_external(const int32_t *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_i32 *out);
+ImbufStatus imbuf_channel_i32_clone(const ImbufChannel_i32 *channel, ImbufChannel_i32 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+int32_t *imbuf_channel_i32_make_mut(ImbufChannel_i32 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_i32_drop(ImbufChannel_i32 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_i32_import(ImbufChannel_i32 *channel, ImbufChannel_i32 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_i32_import` */
+ImbufStatus imbuf_dynamic_image_new_i32(ImbufChannel_i32 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_i32(ImbufDynamicImage *image, ImbufChannel_i32 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_i32(const ImbufDynamicImage *image, size_t index, ImbufChannel_i32 *out);
+
+/* f32 */
+
+struct ImbufChannel_f32;
+
+typedef struct ImbufChannelVTable_f32 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_f32 (*clone)(const struct ImbufChannel_f32 *channel);
+    void (*make_mut)(struct ImbufChannel_f32 *channel);
+    void (*drop)(struct ImbufChannel_f32 *channel);
+} ImbufChannelVTable_f32;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_f32 {
+    const ImbufChannelVTable_f32 *vtable;
+    const float *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_f32;
+
+ImbufStatus imbuf_channel_f32_from_copy(const float *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_f32 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_f32_from_external(const float *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_f32 *out);
+ImbufStatus imbuf_channel_f32_clone(const ImbufChannel_f32 *channel, ImbufChannel_f32 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+float *imbuf_channel_f32_make_mut(ImbufChannel_f32 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_f32_drop(ImbufChannel_f32 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_f32_import(ImbufChannel_f32 *channel, ImbufChannel_f32 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_f32_import` */
+ImbufStatus imbuf_dynamic_image_new_f32(ImbufChannel_f32 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_f32(ImbufDynamicImage *image, ImbufChannel_f32 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_f32(const ImbufDynamicImage *image, size_t index, ImbufChannel_f32 *out);
+
+/* f64 */
+
+struct ImbufChannel_f64;
+
+typedef struct ImbufChannelVTable_f64 {
+    ImbufChannelAbi abi;
+    struct ImbufChannel_f64 (*clone)(const struct ImbufChannel_f64 *channel);
+    void (*make_mut)(struct ImbufChannel_f64 *channel);
+    void (*drop)(struct ImbufChannel_f64 *channel);
+} ImbufChannelVTable_f64;
+
+/* Pixel `x` of row `y` starts at `ptr[y * stride + x * pixel_elements]`. The fields must not be modified. */
+typedef struct ImbufChannel_f64 {
+    const ImbufChannelVTable_f64 *vtable;
+    const double *ptr;
+    uint32_t width;
+    uint32_t height;
+    size_t stride;
+    void *data;
+    uint8_t pixel_elements;
+} ImbufChannel_f64;
+
+ImbufStatus imbuf_channel_f64_from_copy(const double *data, uint32_t width, uint32_t height, size_t stride,
+                                        uint8_t pixel_elements, ImbufChannel_f64 *out);
+/* Doesn't copy. `release(context)` is called from any thread once the last clone is dropped, if `release` is not NULL */
+ImbufStatus imbuf_channel_f64_from_external(const double *data, uint32_t width, uint32_t height, size_t stride,
+                                            uint8_t pixel_elements, ImbufReleaseFn release, void *context,
+                                            ImbufChannel_f64 *out);
+ImbufStatus imbuf_channel_f64_clone(const ImbufChannel_f64 *channel, ImbufChannel_f64 *out);
+/* Returns NULL, if `channel` is NULL. Read `stride` afterwards, because the pixels might have been copied */
+double *imbuf_channel_f64_make_mut(ImbufChannel_f64 *channel);
+/* Sets `ptr` to NULL. Does nothing, if `channel` or its `ptr` is NULL */
+void imbuf_channel_f64_drop(ImbufChannel_f64 *channel);
+/* Moves a channel of another build to `out`, if its ImbufChannelAbi matches. Otherwise `channel` must be released by its creator */
+ImbufStatus imbuf_channel_f64_import(ImbufChannel_f64 *channel, ImbufChannel_f64 *out);
+/* Moves `channel` into a new image after checking its ImbufChannelAbi like `imbuf_channel_f64_import` */
+ImbufStatus imbuf_dynamic_image_new_f64(ImbufChannel_f64 *channel, ImbufDynamicImage **out);
+/* Moves `channel` into `image` after checking its ImbufChannelAbi. On failure, `channel` is left untouched */
+ImbufStatus imbuf_dynamic_image_push_f64(ImbufDynamicImage *image, ImbufChannel_f64 *channel);
+/* Writes a clone of the channel at `index`, which has to be dropped by the caller */
+ImbufStatus imbuf_dynamic_image_get_f64(const ImbufDynamicImage *image, size_t index, ImbufChannel_f64 *out);
+
+/* Returns 0, if `image` is NULL */
+size_t imbuf_dynamic_image_len(const ImbufDynamicImage *image);
+ImbufStatus imbuf_dynamic_image_channel_info(const ImbufDynamicImage *image, size_t index, ImbufChannelInfo *out);
+/* Shares the pixels with `image`. Returns NULL, if `image` is NULL */
+ImbufDynamicImage *imbuf_dynamic_image_clone(const ImbufDynamicImage *image);
+/* Does nothing, if `image` is NULL */
+void imbuf_dynamic_image_drop(ImbufDynamicImage *image);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* IMBUF_H */
@@ -0,0 +1,244 @@
+use std::{
+    alloc::Layout,
+    num::{NonZeroU8, NonZeroU32},
+};
+
+use crate::{
+    Image, ImageChannel, ImageChannels, IncompatibleBufferSizeError, Metadata, PixelType,
+    PixelTypePrimitive,
+    channel::{
+        ChannelFactory, ImageChannelAbi, ImageChannelVTable, UnsafeImageChannel,
+        checked_calc_pixel_len_flat,
+    },
+    pixel_elements::PixelSize,
+};
+
+struct AlignedFactory;
+
+impl<T: PixelTypePrimitive> UnsafeImageChannel<T> {
+    /// Creates a zeroed channel, whose rows all start at a multiple of `align` bytes
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two or the size overflows `isize`
+    #[must_use]
+    pub fn zeroed_aligned(
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+        align: usize,
+    ) -> Self {
+        let align = align.max(align_of::<T>());
+        let stride = checked_calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements)
+            .and_then(|len| len.checked_mul(size_of::<T>()))
+            .and_then(|row_bytes| row_bytes.checked_next_multiple_of(align.max(size_of::<T>())))
+            .expect(SIZE_OVERFLOW)
+            / size_of::<T>();
+        let layout = aligned_layout::<T>(stride, height, align);
+        // Safety: Zero is a valid value for all primitives
+        let ptr = unsafe { std::alloc::alloc_zeroed(layout) };
+        if ptr.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
+        let vtable = <AlignedFactory as ChannelFactory<T>>::VTABLE;
+        unsafe {
+            Self::new_with_vtable(
+                ptr.cast(),
+                width,
+                height,
+                stride,
+                pixel_elements,
+                vtable,
+                std::ptr::without_provenance_mut(align),
+            )
+        }
+    }
+}
+
+const SIZE_OVERFLOW: &str = "size of aligned channel overflows isize";
+
+fn aligned_layout<T>(stride: usize, height: NonZeroU32, align: usize) -> Layout {
+    assert!(align.is_power_of_two(), "align must be a power of two");
+    stride
+        .checked_mul(height.get() as usize)
+        .and_then(|len| len.checked_mul(size_of::<T>()))
+        .and_then(|size| Layout::from_size_align(size, align).ok())
+        .expect(SIZE_OVERFLOW)
+}
+
+impl<T: PixelTypePrimitive> ChannelFactory<T> for AlignedFactory {
+    const VTABLE: &'static ImageChannelVTable<T> = {
+        unsafe extern "C" fn make_mut<T: Clone>(_image: &mut UnsafeImageChannel<T>) {
+            // Do nothing, as the allocation is exclusively owned by the channel
+        }
+        extern "C" fn clear_aligned_channel<T>(image: &mut UnsafeImageChannel<T>) {
+            let layout = aligned_layout::<T>(image.stride, image.height, image.data as usize);
+            // Primitives don't need to be dropped
+            unsafe { std::alloc::dealloc(image.ptr.cast_mut().cast(), layout) };
+        }
+        extern "C" fn clone_aligned_channel<T: PixelTypePrimitive>(
+            image: &UnsafeImageChannel<T>,
+        ) -> UnsafeImageChannel<T> {
+            let result = UnsafeImageChannel::<T>::zeroed_aligned(
+                image.width,
+                image.height,
+                image.pixel_elements,
+                image.data as usize,
+            );
+            let len = result.row_len_flat();
+            for (y, row) in (0..image.height.get()).zip(image.rows_flat()) {
+                let target =
+                    unsafe { std::slice::from_raw_parts_mut(result.row_ptr(y).cast_mut(), len) };
+                target.clone_from_slice(row);
+            }
+            result
+        }
+        &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
+            make_mut,
+            drop: clear_aligned_channel,
+            clone: clone_aligned_channel,
+        }
+    };
+}
+
+impl<TP: PixelType> ImageChannel<TP> {
+    /// Copies the packed `input` into rows, which all start at a multiple of `align` bytes.
+    /// Rows are padded if necessary, so the channel might not be contiguous.
+    ///
+    /// Clones keep the alignment.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two, the size overflows `isize` or the buffer size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_aligned(input: &[TP], width: NonZeroU32, height: NonZeroU32, align: usize) -> Self {
+        match Self::try_new_aligned(input, width, height, align) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Like [`Self::new_aligned`]
+    ///
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two or the size overflows `isize`
+    pub fn try_new_aligned(
+        input: &[TP],
+        width: NonZeroU32,
+        height: NonZeroU32,
+        align: usize,
+    ) -> Result<Self, IncompatibleBufferSizeError<&[TP]>> {
+        let input = IncompatibleBufferSizeError::check(
+            input,
+            checked_calc_pixel_len_flat(width, height, NonZeroU8::MIN),
+            |x| x.len(),
+        )?;
+        let mut result = Self::zeroed_aligned(width, height, align);
+        for (target, source) in result
+            .rows_mut()
+            .zip(input.chunks_exact(width.get() as usize))
+        {
+            target.clone_from_slice(source);
+        }
+        Ok(result)
+    }
+
+    /// Creates a zeroed channel, whose rows all start at a multiple of `align` bytes
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two or the size overflows `isize`
+    #[must_use]
+    pub fn zeroed_aligned(width: NonZeroU32, height: NonZeroU32, align: usize) -> Self {
+        Self(UnsafeImageChannel::zeroed_aligned(
+            width,
+            height,
+            TP::PixelSize::default().get(),
+            align,
+        ))
+    }
+}
+
+impl<T: PixelType, const CHANNELS: usize> Image<T, CHANNELS> {
+    /// Creates a zeroed image, whose rows all start at a multiple of `align` bytes
+    ///
+    /// # Panics
+    /// Panics if `align` is not a power of two or the size overflows `isize`
+    #[must_use]
+    pub fn zeroed_aligned(width: NonZeroU32, height: NonZeroU32, align: usize) -> Self {
+        ImageChannels(
+            std::array::from_fn(|_| ImageChannel::zeroed_aligned(width, height, align)),
+            Metadata::new(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use crate::{Image, ImageChannel};
+
+    #[test]
+    fn rows_are_aligned() {
+        let three = NonZeroU32::new(3).unwrap();
+        let channel = ImageChannel::<u16>::new_aligned(
+            &[1, 2, 3, 4, 5, 6],
+            three,
+            NonZeroU32::new(2).unwrap(),
+            64,
+        );
+        assert_eq!(channel.stride(), 32);
+        assert!(!channel.is_contiguous());
+        assert_eq!(channel.alignment(), 64);
+        assert_eq!(channel.rows().collect::<Vec<_>>(), [[1, 2, 3], [4, 5, 6]]);
+        for row in channel.rows() {
+            assert!(row.as_ptr().addr().is_multiple_of(64));
+        }
+    }
+
+    #[test]
+    fn clone_and_make_mut_keep_alignment() {
+        let mut channel = ImageChannel::<[f32; 3]>::zeroed_aligned(
+            NonZeroU32::new(5).unwrap(),
+            NonZeroU32::new(3).unwrap(),
+            32,
+        );
+        let ptr = channel.rows().next().unwrap().as_ptr();
+        let clone = channel.clone();
+        assert_eq!(clone.alignment(), 32);
+        assert_eq!(clone.stride(), channel.stride());
+
+        channel.rows_mut().next().unwrap()[0] = [1.0; 3];
+        assert_eq!(channel.rows().next().unwrap().as_ptr(), ptr);
+        assert_eq!(channel.get_pixel(0, 0), Some(&[1.0; 3]));
+        assert_eq!(clone.get_pixel(0, 0), Some(&[0.0; 3]));
+    }
+
+    #[test]
+    fn zeroed_aligned_image() {
+        let image = Image::<u8, 3>::zeroed_aligned(
+            NonZeroU32::new(64).unwrap(),
+            NonZeroU32::new(2).unwrap(),
+            64,
+        );
+        for channel in image.into_channels() {
+            assert!(channel.is_contiguous());
+            assert!(channel.alignment() >= 64);
+            assert!(channel.rows_flat().flatten().all(|x| *x == 0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "power of two")]
+    fn reject_invalid_alignment() {
+        let _ = ImageChannel::<u8>::zeroed_aligned(NonZeroU32::MIN, NonZeroU32::MIN, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows isize")]
+    fn reject_overflowing_size() {
+        let _ = ImageChannel::<[f64; 4]>::zeroed_aligned(NonZeroU32::MAX, NonZeroU32::MAX, 64);
+    }
+}
@@ -4,13 +4,19 @@ use std::{
     sync::Arc,
 };
 
-use crate::channel::{ChannelFactory, ImageChannelVTable, UnsafeImageChannel, calc_pixel_len_flat};
+use crate::{
+    PixelTypePrimitive,
+    channel::{
+        ChannelFactory, ImageChannelAbi, ImageChannelVTable, IncompatibleBufferSizeError,
+        UnsafeImageChannel, calc_pixel_len_flat, checked_calc_pixel_len_flat,
+    },
+};
 
 struct ArcFactory;
 
 impl<T> UnsafeImageChannel<T>
 where
-    T: Clone + 'static,
+    T: PixelTypePrimitive,
 {
     #[must_use]
     /// # Panics
@@ -21,31 +27,45 @@ where
         height: NonZeroU32,
         pixel_elements: NonZeroU8,
     ) -> Self {
-        let len = input.len();
-        assert_eq!(
-            len,
-            calc_pixel_len_flat(width, height, pixel_elements),
-            "Incompatible Buffer-Size"
-        );
+        match Self::try_new_arc(input, width, height, pixel_elements) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
 
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the `width`, `height` and `pixel_elements`.
+    pub fn try_new_arc(
+        input: Arc<[T]>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Result<Self, IncompatibleBufferSizeError<Arc<[T]>>> {
+        let input = IncompatibleBufferSizeError::check(
+            input,
+            checked_calc_pixel_len_flat(width, height, pixel_elements),
+            |x| x.len(),
+        )?;
+        let len = input.len();
         let ptr = Arc::into_raw(input).cast::<T>();
         let vtable = <ArcFactory as ChannelFactory<T>>::VTABLE;
-        unsafe {
+        Ok(unsafe {
             Self::new_with_vtable(
                 ptr,
                 width,
                 height,
+                calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
                 pixel_elements,
                 vtable,
                 std::ptr::without_provenance_mut(len),
             )
-        }
+        })
     }
 }
 
-impl<T: 'static + Clone> ChannelFactory<T> for ArcFactory {
+impl<T: PixelTypePrimitive> ChannelFactory<T> for ArcFactory {
     const VTABLE: &'static ImageChannelVTable<T> = {
-        unsafe extern "C" fn make_mut<T: Clone>(image: &mut UnsafeImageChannel<T>) {
+        unsafe extern "C" fn make_mut<T: PixelTypePrimitive>(image: &mut UnsafeImageChannel<T>) {
             let mut arc = ManuallyDrop::new(unsafe {
                 let ptr = std::ptr::slice_from_raw_parts(image.ptr, image.data as usize);
                 Arc::<[T]>::from_raw(ptr)
@@ -58,14 +78,14 @@ impl<T: 'static + Clone> ChannelFactory<T> for ArcFactory {
                 image.ptr = Arc::into_raw(new_data).cast::<T>();
             }
         }
-        extern "C" fn clear_arc_channel<T: Clone>(image: &mut UnsafeImageChannel<T>) {
+        extern "C" fn clear_arc_channel<T: PixelTypePrimitive>(image: &mut UnsafeImageChannel<T>) {
             unsafe {
                 let ptr = std::ptr::slice_from_raw_parts(image.ptr, image.data as usize);
                 Arc::<[T]>::from_raw(ptr);
             }
         }
 
-        extern "C" fn clone_arc_channel<T: Clone>(
+        extern "C" fn clone_arc_channel<T: PixelTypePrimitive>(
             image: &UnsafeImageChannel<T>,
         ) -> UnsafeImageChannel<T> {
             let arc = ManuallyDrop::new(unsafe {
@@ -81,6 +101,7 @@ impl<T: 'static + Clone> ChannelFactory<T> for ArcFactory {
         }
 
         &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
             drop: clear_arc_channel,
             clone: clone_arc_channel,
             make_mut,
@@ -88,13 +109,83 @@ impl<T: 'static + Clone> ChannelFactory<T> for ArcFactory {
     };
 }
 
-pub(crate) extern "C" fn clone_slice_into_arc_channel<T: Clone>(
+/// Keeps the pixels alive by an `Arc<TOwner>` in `data` (e.g. a memory map or foreign memory).
+/// The owner might still read the pixels or they might be read-only, so `make_mut` always copies them into a Vec.
+#[cfg(any(feature = "memmap2", feature = "ffi", feature = "dlpack"))]
+pub(crate) struct ArcOwnerFactory<TOwner>(std::marker::PhantomData<TOwner>);
+
+#[cfg(any(feature = "memmap2", feature = "ffi", feature = "dlpack"))]
+impl<T: PixelTypePrimitive> UnsafeImageChannel<T> {
+    /// # Safety
+    /// `ptr` must be valid for reading `height` rows of `width * pixel_elements` elements, which are `stride` elements apart, as long as `owner` is alive.
+    pub(crate) unsafe fn new_arc_owner<TOwner: Send + Sync + 'static>(
+        ptr: *const T,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        stride: usize,
+        pixel_elements: NonZeroU8,
+        owner: Arc<TOwner>,
+    ) -> Self {
+        unsafe {
+            Self::new_with_vtable(
+                ptr,
+                width,
+                height,
+                stride,
+                pixel_elements,
+                <ArcOwnerFactory<TOwner> as ChannelFactory<T>>::VTABLE,
+                Arc::into_raw(owner).cast_mut().cast(),
+            )
+        }
+    }
+}
+
+#[cfg(any(feature = "memmap2", feature = "ffi", feature = "dlpack"))]
+impl<T: PixelTypePrimitive, TOwner: Send + Sync + 'static> ChannelFactory<T>
+    for ArcOwnerFactory<TOwner>
+{
+    const VTABLE: &'static ImageChannelVTable<T> = {
+        unsafe extern "C" fn make_mut<T: PixelTypePrimitive>(image: &mut UnsafeImageChannel<T>) {
+            *image = UnsafeImageChannel::new_vec(
+                image.to_vec_flat(),
+                image.width,
+                image.height,
+                image.pixel_elements,
+            );
+        }
+        unsafe extern "C" fn clear_owner_channel<T, TOwner>(image: &mut UnsafeImageChannel<T>) {
+            unsafe { drop(Arc::from_raw(image.data.cast::<TOwner>())) };
+        }
+        unsafe extern "C" fn clone_owner_channel<T, TOwner>(
+            image: &UnsafeImageChannel<T>,
+        ) -> UnsafeImageChannel<T> {
+            unsafe { Arc::increment_strong_count(image.data.cast::<TOwner>()) };
+            UnsafeImageChannel {
+                vtable: image.vtable,
+                ptr: image.ptr,
+                width: image.width,
+                height: image.height,
+                stride: image.stride,
+                data: image.data,
+                pixel_elements: image.pixel_elements,
+            }
+        }
+        &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
+            clone: clone_owner_channel::<T, TOwner>,
+            make_mut,
+            drop: clear_owner_channel::<T, TOwner>,
+        }
+    };
+}
+
+pub(crate) extern "C" fn clone_slice_into_arc_channel<T: PixelTypePrimitive>(
     image: &UnsafeImageChannel<T>,
 ) -> UnsafeImageChannel<T> {
-    UnsafeImageChannel::new_arc(
-        Arc::from(image.buffer_flat()),
-        image.width,
-        image.height,
-        image.pixel_elements,
-    )
+    let data: Arc<[T]> = if image.is_contiguous() {
+        Arc::from(image.buffer_flat())
+    } else {
+        Arc::from(image.to_vec_flat())
+    };
+    UnsafeImageChannel::new_arc(data, image.width, image.height, image.pixel_elements)
 }
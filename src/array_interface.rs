@@ -0,0 +1,203 @@
+use crate::{
+    DynamicImageChannel, DynamicSize, ImageChannel, PixelTypePrimitive,
+    dynamic::match_dynamic_channel, pixel::RuntimePixelType,
+};
+
+/// Fields of `NumPy`'s `__array_interface__` (version 3) for a channel
+///
+/// Pixels are described as `(height, width, pixel_elements)` in C order, so bindings have to squeeze single element channels themselves.
+/// `bf16` is described as raw 2 byte values (`V2`), because `NumPy` has no such type.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArrayInterface {
+    pub shape: [usize; 3],
+    /// In bytes
+    pub strides: [usize; 3],
+    /// e.g. `|u1`, `<f4` or `|V2`
+    pub typestr: String,
+    /// Address of the first pixel
+    pub data: usize,
+    pub readonly: bool,
+}
+
+impl ArrayInterface {
+    fn of<TP: RuntimePixelType>(channel: &ImageChannel<TP>, readonly: bool) -> Self {
+        let size = size_of::<TP::Primitive>();
+        let pixel_elements = usize::from(channel.pixel_elements().get());
+        Self {
+            shape: [
+                channel.height().get() as usize,
+                channel.width().get() as usize,
+                pixel_elements,
+            ],
+            strides: [channel.stride() * size, pixel_elements * size, size],
+            typestr: typestr::<TP::Primitive>(),
+            data: channel.0.ptr as usize,
+            readonly,
+        }
+    }
+}
+
+/// `NumPy`'s type string in native byte order (e.g. `<u2`)
+pub(crate) fn typestr<T: PixelTypePrimitive>() -> String {
+    let size = size_of::<T>();
+    // Raw bytes (`V`) have no byte order
+    let byteorder = if size == 1 || T::ARRAY_KIND == 'V' {
+        '|'
+    } else if cfg!(target_endian = "little") {
+        '<'
+    } else {
+        '>'
+    };
+    format!("{byteorder}{}{size}", T::ARRAY_KIND)
+}
+
+/// Keeps exported pixels alive, e.g. by moving it into the Python object, which provides `__array_interface__`
+#[derive(Debug)]
+pub struct ArrayExport {
+    interface: ArrayInterface,
+    channel: DynamicImageChannel,
+}
+
+impl ArrayExport {
+    #[must_use]
+    pub fn interface(&self) -> &ArrayInterface {
+        &self.interface
+    }
+
+    /// Ends the export. The pixels must no longer be accessed through the interface afterwards
+    #[must_use]
+    pub fn into_channel(self) -> DynamicImageChannel {
+        self.channel
+    }
+}
+
+impl<TP: RuntimePixelType> ImageChannel<TP> {
+    /// Moves the channel into the export without copying, so the interface is read-only
+    #[must_use]
+    pub fn into_array_export(self) -> ArrayExport {
+        self.into_export(true)
+    }
+
+    /// Exports a clone, which is created through the vtable (see [`ImageChannel::into_array_export`])
+    ///
+    /// Like every clone, this copies channels backed by a `Vec`
+    #[must_use]
+    pub fn export_array(&self) -> ArrayExport {
+        self.clone().into_array_export()
+    }
+
+    /// Ensures exclusive access to the pixels first (see [`ImageChannel::make_mut`]), so the interface is writable.
+    /// Doesn't copy, if the pixels aren't shared.
+    #[must_use]
+    pub fn into_writable_array_export(mut self) -> ArrayExport {
+        unsafe { (self.0.vtable.make_mut)(&mut self.0) };
+        self.into_export(false)
+    }
+
+    fn into_export(self, readonly: bool) -> ArrayExport {
+        let interface = ArrayInterface::of(&self, readonly);
+        let channel =
+            TP::Primitive::into_runtime_channel(ImageChannel::<DynamicSize<TP::Primitive>>(self.0));
+        ArrayExport { interface, channel }
+    }
+}
+
+impl DynamicImageChannel {
+    /// See [`ImageChannel::into_array_export`]
+    #[must_use]
+    pub fn into_array_export(self) -> ArrayExport {
+        match_dynamic_channel!(self, x => x.into_array_export())
+    }
+
+    /// See [`ImageChannel::export_array`]
+    #[must_use]
+    pub fn export_array(&self) -> ArrayExport {
+        match_dynamic_channel!(self, x => x.export_array())
+    }
+
+    /// See [`ImageChannel::into_writable_array_export`]
+    #[must_use]
+    pub fn into_writable_array_export(self) -> ArrayExport {
+        match_dynamic_channel!(self, x => x.into_writable_array_export())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use crate::{DynamicImageChannel, Image, ImageChannel};
+
+    #[test]
+    fn export_vec_channel_without_copy() {
+        let channel = ImageChannel::<[u16; 3]>::new_vec(
+            vec![[1, 2, 3], [4, 5, 6]],
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let ptr = channel.buffer().as_ptr() as usize;
+        let export = DynamicImageChannel::from(channel).into_array_export();
+        assert_eq!(export.interface().data, ptr);
+        assert!(export.interface().readonly);
+        let channel = ImageChannel::<[u16; 3]>::try_from(export.into_channel()).unwrap();
+        assert_eq!(channel.buffer(), &[[1, 2, 3], [4, 5, 6]]);
+    }
+
+    #[test]
+    fn describes_interleaved_channel() {
+        let channel = ImageChannel::<[u16; 3]>::new_arc(
+            [[1, 2, 3], [4, 5, 6]].into(),
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let export = channel.export_array();
+        let interface = export.interface();
+        assert_eq!(interface.shape, [1, 2, 3]);
+        assert_eq!(interface.strides, [12, 6, 2]);
+        assert_eq!(
+            interface.typestr,
+            if cfg!(target_endian = "little") {
+                "<u2"
+            } else {
+                ">u2"
+            }
+        );
+        assert_eq!(interface.data, channel.buffer().as_ptr() as usize);
+        assert!(interface.readonly);
+        assert_eq!(
+            ImageChannel::<[u16; 3]>::try_from(export.into_channel()).unwrap(),
+            channel
+        );
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn bf16_has_no_byte_order() {
+        assert_eq!(super::typestr::<half::bf16>(), "|V2");
+        assert_eq!(&super::typestr::<half::f16>()[1..], "f2");
+    }
+
+    #[test]
+    fn writable_export_owns_pixels() {
+        let image = Image::<i8, 2>::new_vec(
+            vec![1, 2, 3, 4],
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let [first, _] = image.clone().into_channels();
+        let shared_ptr = first.buffer().as_ptr() as usize;
+
+        let export = DynamicImageChannel::from(first).into_writable_array_export();
+        let interface = export.interface();
+        assert_eq!(interface.typestr, "|i1");
+        assert_eq!(interface.shape, [1, 2, 1]);
+        assert!(!interface.readonly);
+        assert_ne!(interface.data, shared_ptr);
+        assert_eq!(image.buffers()[0], [1, 2]);
+
+        let [_, second] = image.into_channels();
+        let ptr = second.buffer().as_ptr() as usize;
+        assert_eq!(second.into_writable_array_export().interface().data, ptr);
+    }
+}
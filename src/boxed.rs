@@ -0,0 +1,109 @@
+use std::num::{NonZeroU8, NonZeroU32};
+
+use crate::{
+    PixelTypePrimitive,
+    channel::{
+        ChannelFactory, ImageChannelAbi, ImageChannelVTable, IncompatibleBufferSizeError,
+        UnsafeImageChannel, calc_pixel_len_flat, checked_calc_pixel_len_flat,
+    },
+};
+
+struct BoxFactory;
+
+impl<T> UnsafeImageChannel<T>
+where
+    T: PixelTypePrimitive,
+{
+    /// # Panics
+    /// Panics if the buffer size is not compatible with the `width`, `height` and `pixel_elements`.
+    #[must_use]
+    pub fn new_box(
+        input: Box<[T]>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Self {
+        match Self::try_new_box(input, width, height, pixel_elements) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the `width`, `height` and `pixel_elements`.
+    pub fn try_new_box(
+        input: Box<[T]>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Result<Self, IncompatibleBufferSizeError<Box<[T]>>> {
+        let input = IncompatibleBufferSizeError::check(
+            input,
+            checked_calc_pixel_len_flat(width, height, pixel_elements),
+            |x| x.len(),
+        )?;
+        let len = input.len();
+        let ptr = Box::into_raw(input).cast::<T>();
+        let vtable = <BoxFactory as ChannelFactory<T>>::VTABLE;
+        Ok(unsafe {
+            Self::new_with_vtable(
+                ptr,
+                width,
+                height,
+                calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
+                pixel_elements,
+                vtable,
+                std::ptr::without_provenance_mut(len),
+            )
+        })
+    }
+}
+
+impl<T: PixelTypePrimitive> ChannelFactory<T> for BoxFactory {
+    const VTABLE: &'static ImageChannelVTable<T> = {
+        unsafe extern "C" fn make_mut<T: Clone>(_image: &mut UnsafeImageChannel<T>) {
+            // Do nothing, as the box is exclusively owned by the channel
+        }
+        extern "C" fn clear_box_channel<T>(image: &mut UnsafeImageChannel<T>) {
+            unsafe {
+                let ptr =
+                    std::ptr::slice_from_raw_parts_mut(image.ptr.cast_mut(), image.data as usize);
+                drop(Box::<[T]>::from_raw(ptr));
+            }
+        }
+        &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
+            make_mut,
+            drop: clear_box_channel,
+            clone: crate::arc::clone_slice_into_arc_channel,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use crate::ImageChannel;
+
+    #[test]
+    fn make_mut_keeps_allocation() {
+        let two = NonZeroU32::new(2).unwrap();
+        let mut channel =
+            ImageChannel::<[u8; 2]>::new_box(vec![[1, 2], [3, 4]].into(), two, NonZeroU32::MIN);
+        let ptr = channel.buffer().as_ptr();
+        let clone = channel.clone();
+        channel.make_mut()[0] = [5, 6];
+        assert_eq!(channel.buffer().as_ptr(), ptr);
+        assert_eq!(channel.buffer(), &[[5, 6], [3, 4]]);
+        assert_eq!(clone.buffer(), &[[1, 2], [3, 4]]);
+    }
+
+    #[test]
+    fn try_new_box_returns_input() {
+        let err =
+            ImageChannel::<u8>::try_new_box(vec![1, 2, 3].into(), NonZeroU32::MIN, NonZeroU32::MIN)
+                .unwrap_err();
+        assert_eq!(&*err.buffer, &[1, 2, 3]);
+    }
+}
@@ -0,0 +1,238 @@
+use std::num::{NonZeroU8, NonZeroU32};
+
+use bytes::Bytes;
+
+use crate::{
+    ImageChannel, IncompatibleBufferSizeError, PixelType, PixelTypePrimitive,
+    channel::{
+        ChannelFactory, ImageChannelAbi, ImageChannelVTable, UnsafeImageChannel,
+        calc_pixel_len_flat, checked_calc_pixel_len_flat,
+    },
+    pixel_elements::PixelSize,
+};
+
+struct BytesFactory;
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum BytesChannelError {
+    #[error(transparent)]
+    IncompatibleBufferSize(#[from] IncompatibleBufferSizeError<Bytes>),
+    #[error("Bytes are not aligned to {align} bytes")]
+    Misaligned { bytes: Bytes, align: usize },
+}
+
+impl<T: PixelTypePrimitive> UnsafeImageChannel<T> {
+    /// Creates a channel which keeps `input` alive by refcount. Pixels are interpreted in native endianness.
+    ///
+    /// # Panics
+    /// Panics if `input` is not aligned for `T` or its size is not compatible with the `width`, `height` and `pixel_elements`.
+    #[must_use]
+    pub fn new_bytes(
+        input: Bytes,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Self {
+        match Self::try_new_bytes(input, width, height, pixel_elements) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// Creates a channel which keeps `input` alive by refcount. Pixels are interpreted in native endianness.
+    ///
+    /// # Errors
+    /// Returns the `input`, if it is not aligned for `T` or its size is not compatible with the `width`, `height` and `pixel_elements`.
+    pub fn try_new_bytes(
+        input: Bytes,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Result<Self, BytesChannelError> {
+        let input = IncompatibleBufferSizeError::check(
+            input,
+            checked_calc_pixel_len_flat(width, height, pixel_elements)
+                .and_then(|len| len.checked_mul(size_of::<T>())),
+            Bytes::len,
+        )?;
+        let ptr = input.as_ptr().cast::<T>();
+        if !ptr.is_aligned() {
+            return Err(BytesChannelError::Misaligned {
+                bytes: input,
+                align: align_of::<T>(),
+            });
+        }
+        let vtable = <BytesFactory as ChannelFactory<T>>::VTABLE;
+        Ok(unsafe {
+            Self::new_with_vtable(
+                ptr,
+                width,
+                height,
+                calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
+                pixel_elements,
+                vtable,
+                Box::into_raw(Box::new(input)).cast(),
+            )
+        })
+    }
+}
+
+impl<T: PixelTypePrimitive> ChannelFactory<T> for BytesFactory {
+    const VTABLE: &'static ImageChannelVTable<T> = {
+        unsafe extern "C" fn make_mut<T: PixelTypePrimitive>(image: &mut UnsafeImageChannel<T>) {
+            let bytes = unsafe { &*image.data.cast::<Bytes>() };
+            // Unique Bytes can be reused like `BytesMut::from(Bytes)` does. Static or shared data is copied
+            if !bytes.is_unique() {
+                *image = UnsafeImageChannel::new_vec(
+                    image.to_vec_flat(),
+                    image.width,
+                    image.height,
+                    image.pixel_elements,
+                );
+            }
+        }
+        unsafe extern "C" fn clear_bytes_channel<T>(image: &mut UnsafeImageChannel<T>) {
+            unsafe { drop(Box::from_raw(image.data.cast::<Bytes>())) };
+        }
+        unsafe extern "C" fn clone_bytes_channel<T>(
+            image: &UnsafeImageChannel<T>,
+        ) -> UnsafeImageChannel<T> {
+            let bytes = unsafe { &*image.data.cast::<Bytes>() };
+            UnsafeImageChannel {
+                ptr: image.ptr,
+                width: image.width,
+                height: image.height,
+                stride: image.stride,
+                vtable: image.vtable,
+                data: Box::into_raw(Box::new(bytes.clone())).cast(),
+                pixel_elements: image.pixel_elements,
+            }
+        }
+        &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
+            clone: clone_bytes_channel,
+            make_mut,
+            drop: clear_bytes_channel,
+        }
+    };
+}
+
+impl<TP: PixelType> ImageChannel<TP> {
+    /// Creates a channel which keeps `input` alive by refcount. Pixels are interpreted in native endianness.
+    ///
+    /// [`ImageChannel::make_mut`] only copies, if `input` is shared or static.
+    ///
+    /// # Panics
+    /// Panics if `input` is not aligned for `TP::Primitive` or its size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_bytes(input: Bytes, width: NonZeroU32, height: NonZeroU32) -> Self {
+        Self(UnsafeImageChannel::new_bytes(
+            input,
+            width,
+            height,
+            TP::PixelSize::default().get(),
+        ))
+    }
+
+    /// Creates a channel which keeps `input` alive by refcount. Pixels are interpreted in native endianness.
+    ///
+    /// # Errors
+    /// Returns the `input`, if it is not aligned for `TP::Primitive` or its size is not compatible with the width and height.
+    pub fn try_new_bytes(
+        input: Bytes,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, BytesChannelError> {
+        UnsafeImageChannel::try_new_bytes(input, width, height, TP::PixelSize::default().get())
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use bytes::Bytes;
+
+    use super::BytesChannelError;
+    use crate::ImageChannel;
+
+    #[test]
+    fn clone_shares_bytes() {
+        let bytes = Bytes::from(vec![1u8, 2, 3, 4]);
+        let channel = ImageChannel::<u8>::new_bytes(
+            bytes.slice(1..3),
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let clone = channel.clone();
+        assert_eq!(channel.buffer(), &[2, 3]);
+        assert_eq!(clone.buffer().as_ptr(), bytes[1..].as_ptr());
+    }
+
+    #[test]
+    fn make_mut_copies_shared_bytes() {
+        let bytes = Bytes::from(vec![1u8, 2]);
+        let mut channel =
+            ImageChannel::<[u8; 2]>::new_bytes(bytes.clone(), NonZeroU32::MIN, NonZeroU32::MIN);
+        channel.make_mut()[0] = [3, 4];
+        assert_eq!(channel.buffer(), &[[3, 4]]);
+        assert_eq!(&bytes[..], &[1, 2]);
+    }
+
+    #[test]
+    fn make_mut_reuses_unique_bytes() {
+        let bytes = Bytes::from(vec![1u8, 2]);
+        let ptr = bytes.as_ptr();
+        let mut channel =
+            ImageChannel::<u8>::new_bytes(bytes, NonZeroU32::new(2).unwrap(), NonZeroU32::MIN);
+        channel.make_mut()[0] = 3;
+        assert_eq!(channel.buffer(), &[3, 2]);
+        assert_eq!(channel.buffer().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn make_mut_copies_static_bytes() {
+        static DATA: [u8; 2] = [1, 2];
+        let mut channel = ImageChannel::<u8>::new_bytes(
+            Bytes::from_static(&DATA),
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        channel.make_mut()[0] = 3;
+        assert_eq!(channel.buffer(), &[3, 2]);
+        assert_eq!(DATA, [1, 2]);
+    }
+
+    #[test]
+    fn reject_invalid_bytes() {
+        let bytes = Bytes::from(
+            vec![0u16; 3]
+                .into_iter()
+                .flat_map(u16::to_ne_bytes)
+                .collect::<Vec<_>>(),
+        );
+        let misaligned = bytes.slice(1..5);
+        assert!(matches!(
+            ImageChannel::<u16>::try_new_bytes(
+                misaligned,
+                NonZeroU32::new(2).unwrap(),
+                NonZeroU32::MIN
+            ),
+            Err(BytesChannelError::Misaligned { align: 2, .. })
+        ));
+        let Err(BytesChannelError::IncompatibleBufferSize(e)) =
+            ImageChannel::<u16>::try_new_bytes(bytes.clone(), NonZeroU32::MIN, NonZeroU32::MIN)
+        else {
+            panic!("Expected IncompatibleBufferSize");
+        };
+        assert_eq!((e.expected, e.actual), (Some(2), 6));
+        let Err(BytesChannelError::IncompatibleBufferSize(e)) =
+            ImageChannel::<u16>::try_new_bytes(bytes, NonZeroU32::MAX, NonZeroU32::MAX)
+        else {
+            panic!("Expected IncompatibleBufferSize");
+        };
+        assert_eq!((e.expected, e.actual), (None, 6));
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     borrow::Borrow,
     fmt::{self, Debug, Formatter},
     num::{NonZeroU8, NonZeroU32},
+    ptr::NonNull,
     sync::Arc,
 };
 
@@ -15,7 +16,7 @@ use crate::{
 /// Interleaved RGB images have a single channel, which is a array of pixels (TP: [u8; 3])
 // Repr transparent is important for casting between ImageChannel and UnsafeImageChannel
 #[repr(transparent)]
-pub struct ImageChannel<TP: RuntimePixelType>(UnsafeImageChannel<TP::Primitive>);
+pub struct ImageChannel<TP: RuntimePixelType>(pub(crate) UnsafeImageChannel<TP::Primitive>);
 
 pub trait BorrowableImageChannel:
     Borrow<ImageChannel<Self::Pixel>> + crate::seal::SealedImageChannel
@@ -50,7 +51,7 @@ where
         self.0.width == other.0.width
             && self.0.height == other.0.height
             && self.0.pixel_elements == other.0.pixel_elements
-            && self.buffer_flat() == other.buffer_flat()
+            && self.rows_flat().eq(other.rows_flat())
     }
 }
 
@@ -62,52 +63,167 @@ where
     /// # Panics
     /// Panics if the buffer size is not compatible with the width and height.
     #[must_use]
-    pub fn new_vec(mut input: Vec<TP>, width: NonZeroU32, height: NonZeroU32) -> Self {
-        assert_eq!(
-            input.len(),
-            calc_pixel_len_packed(width, height),
-            "Incompatible Buffer-Size"
-        );
+    pub fn new_vec(input: Vec<TP>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        match Self::try_new_vec(input, width, height) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
 
-        // Cast Vec<TP> to Vec<TP::Primitive>
-        let len = input.len();
-        let cap = input.capacity();
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_vec(
+        input: Vec<TP>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Vec<TP>>> {
+        let expected = checked_calc_pixel_len_packed(width, height);
+        IncompatibleBufferSizeError::check(input, expected, Vec::len).map(|mut input| {
+            // Cast Vec<TP> to Vec<TP::Primitive>
+            let len = input.len();
+            let cap = input.capacity();
+
+            let ptr = input.as_mut_ptr().cast::<TP::Primitive>();
+            let len = len * TP::ELEMENTS.get() as usize;
+            let cap = cap * TP::ELEMENTS.get() as usize;
+            std::mem::forget(input);
+
+            // Safety: TP::Primitive is expected to be an aligned fraction of TP
+            let cast_input = unsafe { Vec::from_raw_parts(ptr, len, cap) };
+
+            Self(UnsafeImageChannel::new_vec(
+                cast_input,
+                width,
+                height,
+                TP::PixelSize::default().get(),
+            ))
+        })
+    }
 
-        let ptr = input.as_mut_ptr().cast::<TP::Primitive>();
-        let len = len * TP::ELEMENTS.get() as usize;
-        let cap = cap * TP::ELEMENTS.get() as usize;
-        std::mem::forget(input);
+    /// # Panics
+    /// Panics if the buffer size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_arc(input: Arc<[TP]>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        match Self::try_new_arc(input, width, height) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
 
-        // Safety: TP::Primitive is expected to be an aligned fraction of TP
-        let cast_input = unsafe { Vec::from_raw_parts(ptr, len, cap) };
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_arc(
+        input: Arc<[TP]>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Arc<[TP]>>> {
+        let expected = checked_calc_pixel_len_packed(width, height);
+        IncompatibleBufferSizeError::check(input, expected, |x| x.len()).map(|input| {
+            let len = input.len();
+            let ptr = Arc::into_raw(input).cast::<TP::Primitive>();
+            let len = len * TP::ELEMENTS.get() as usize;
+
+            // Safety: TP::Primitive is expected to be an aligned fraction of TP
+            let cast_input = unsafe { Arc::from_raw(std::ptr::slice_from_raw_parts(ptr, len)) };
+
+            Self(UnsafeImageChannel::new_arc(
+                cast_input,
+                width,
+                height,
+                TP::PixelSize::default().get(),
+            ))
+        })
+    }
 
-        Self(UnsafeImageChannel::new_vec(
-            cast_input,
-            width,
-            height,
-            TP::PixelSize::default().get(),
-        ))
+    /// # Panics
+    /// Panics if the buffer size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_box(input: Box<[TP]>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        match Self::try_new_box(input, width, height) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
     }
 
-    pub fn new_arc(input: Arc<[TP]>, width: NonZeroU32, height: NonZeroU32) -> Self {
-        let len = input.len();
-        let ptr = Arc::into_raw(input).cast::<TP::Primitive>();
-        let len = len * TP::ELEMENTS.get() as usize;
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_box(
+        input: Box<[TP]>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Box<[TP]>>> {
+        let expected = checked_calc_pixel_len_packed(width, height);
+        IncompatibleBufferSizeError::check(input, expected, |x| x.len()).map(|input| {
+            let len = input.len();
+            let ptr = Box::into_raw(input).cast::<TP::Primitive>();
+            let len = len * TP::ELEMENTS.get() as usize;
+
+            // Safety: TP::Primitive is expected to be an aligned fraction of TP
+            let cast_input = unsafe { Box::from_raw(std::ptr::slice_from_raw_parts_mut(ptr, len)) };
+
+            Self(UnsafeImageChannel::new_box(
+                cast_input,
+                width,
+                height,
+                TP::PixelSize::default().get(),
+            ))
+        })
+    }
 
-        // Safety: TP::Primitive is expected to be an aligned fraction of TP
-        let cast_input = unsafe { Arc::from_raw(std::ptr::slice_from_raw_parts(ptr, len)) };
+    /// # Panics
+    /// Panics if the rows are not contiguous in memory. Use [`Self::try_buffer`] or [`Self::rows`] for strided channels.
+    #[must_use]
+    pub const fn buffer(&self) -> &[TP] {
+        self.try_buffer()
+            .expect("Channel is not contiguous, use rows() instead")
+    }
 
-        Self(UnsafeImageChannel::new_arc(
-            cast_input,
-            width,
-            height,
-            TP::PixelSize::default().get(),
-        ))
+    /// Returns `None`, if the rows are not contiguous in memory
+    #[must_use]
+    pub const fn try_buffer(&self) -> Option<&[TP]> {
+        if self.0.is_contiguous() {
+            Some(unsafe { std::slice::from_raw_parts(self.0.ptr.cast::<TP>(), self.len()) })
+        } else {
+            None
+        }
     }
 
+    /// Returns the pixels of row `y`, or `None` if `y` is out of bounds
     #[must_use]
-    pub const fn buffer(&self) -> &[TP] {
-        unsafe { std::slice::from_raw_parts(self.0.ptr.cast::<TP>(), self.len()) }
+    pub fn row(&self, y: u32) -> Option<&[TP]> {
+        (y < self.0.height.get()).then(|| unsafe {
+            std::slice::from_raw_parts(self.0.row_ptr(y).cast::<TP>(), self.0.width.get() as usize)
+        })
+    }
+
+    /// Returns `None` if (`x`, `y`) is out of bounds
+    #[must_use]
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<&TP> {
+        self.row(y)?.get(x as usize)
+    }
+
+    /// Returns `None` if (`x`, `y`) is out of bounds. Ensures exclusive access to the buffer first (see [`Self::make_mut`])
+    pub fn get_pixel_mut(&mut self, x: u32, y: u32) -> Option<&mut TP> {
+        if x >= self.0.width.get() || y >= self.0.height.get() {
+            return None;
+        }
+        self.rows_mut().nth(y as usize)?.get_mut(x as usize)
+    }
+
+    /// Iterates over all rows from top to bottom. Works for strided and contiguous channels
+    #[must_use]
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[TP]> + '_ {
+        self.rows_flat().map(|row| unsafe {
+            std::slice::from_raw_parts(row.as_ptr().cast::<TP>(), self.0.width.get() as usize)
+        })
+    }
+
+    /// Like [`Self::rows`], but ensures exclusive access to the buffer first (see [`Self::make_mut`])
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [TP]> + '_ {
+        let width = self.0.width.get() as usize;
+        self.primitive_rows_mut().map(move |row| unsafe {
+            std::slice::from_raw_parts_mut(row.as_mut_ptr().cast::<TP>(), width)
+        })
     }
 
     #[must_use]
@@ -116,7 +232,13 @@ where
         self.0.calc_len_packed()
     }
 
+    /// # Panics
+    /// Panics if the rows are not contiguous in memory. Use [`Self::rows_mut`] for strided channels.
     pub fn make_mut(&mut self) -> &mut [TP] {
+        assert!(
+            self.0.is_contiguous(),
+            "Channel is not contiguous, use rows_mut() instead"
+        );
         unsafe {
             (self.0.vtable.make_mut)(&mut self.0);
             let len = self.len_flat();
@@ -140,9 +262,7 @@ where
             std::mem::forget(self);
             result
         } else {
-            let len = self.len_flat();
-            let buf = unsafe { std::slice::from_raw_parts(self.0.ptr, len) };
-            buf.to_vec()
+            self.0.to_vec_flat()
         };
 
         // Cast Vec<TP::Primitive> back to Vec<TP>
@@ -158,12 +278,20 @@ where
     ///
     /// Use/provide methods like `new_vec()` and `new_arc()` for safe construction
     ///
+    /// `stride` is the distance between the start of two consecutive rows in `TP::Primitive`s.
+    /// Use `width * TP::ELEMENTS` for packed buffers.
+    ///
     /// # Safety
     /// The vtable must be able to cleanup the fields
+    /// `ptr` must be valid for reading `height` rows of `width` pixels, each `stride` primitives apart
+    ///
+    /// # Panics
+    /// Panics if `stride` is smaller than a row of pixels
     pub unsafe fn new_with_vtable(
         ptr: *const TP::Primitive,
         width: NonZeroU32,
         height: NonZeroU32,
+        stride: usize,
         vtable: &'static ImageChannelVTable<TP::Primitive>,
         data: *mut (),
     ) -> Self
@@ -175,6 +303,7 @@ where
                 ptr,
                 width,
                 height,
+                stride,
                 TP::PixelSize::default().get(),
                 vtable,
                 data,
@@ -207,8 +336,8 @@ impl<TP: PixelType> TryFrom<&DynamicImageChannel> for &ImageChannel<TP> {
             <TP::Primitive as PixelTypePrimitive>::try_from_dynamic_image_ref(value).ok_or(())?;
 
         if typed.0.pixel_elements == TP::ELEMENTS {
-            // Safety: ImageChannel is repr(transparent), so we are allowed to transmute between them
-            Ok(unsafe { std::mem::transmute(&typed.0) })
+            // Safety: ImageChannel is repr(transparent), so we are allowed to cast between them
+            Ok(unsafe { &*std::ptr::from_ref(&typed.0).cast::<ImageChannel<TP>>() })
         } else {
             Err(())
         }
@@ -223,8 +352,8 @@ impl<TP: PixelType> TryFrom<&mut DynamicImageChannel> for &mut ImageChannel<TP>
             <TP::Primitive as PixelTypePrimitive>::try_from_dynamic_image_mut(value).ok_or(())?;
 
         if typed.0.pixel_elements == TP::ELEMENTS {
-            // Safety: ImageChannel is repr(transparent), so we are allowed to transmute between them
-            Ok(unsafe { std::mem::transmute(&mut typed.0) })
+            // Safety: ImageChannel is repr(transparent), so we are allowed to cast between them
+            Ok(unsafe { &mut *std::ptr::from_mut(&mut typed.0).cast::<ImageChannel<TP>>() })
         } else {
             Err(())
         }
@@ -251,24 +380,45 @@ impl<TP: RuntimePixelType> ImageChannel<TP> {
         self.0.calc_len_flat()
     }
 
+    /// # Panics
+    /// Panics if the rows are not contiguous in memory. Use [`Self::try_buffer_flat`] or [`Self::rows_flat`] for strided channels.
     #[must_use]
     pub const fn buffer_flat(&self) -> &[TP::Primitive] {
-        let len = self.len_flat();
-        unsafe { std::slice::from_raw_parts(self.0.ptr, len) }
+        self.try_buffer_flat()
+            .expect("Channel is not contiguous, use rows_flat() instead")
     }
 
+    /// Returns `None`, if the rows are not contiguous in memory
     #[must_use]
-    pub const fn buffer_flat_bytes(&self) -> &[u8] {
-        let len = self.len_flat();
-        unsafe {
-            std::slice::from_raw_parts(
-                self.0.ptr.cast(),
-                len * std::mem::size_of::<TP::Primitive>(),
-            )
+    pub const fn try_buffer_flat(&self) -> Option<&[TP::Primitive]> {
+        if self.0.is_contiguous() {
+            Some(self.0.buffer_flat())
+        } else {
+            None
         }
     }
 
+    /// # Panics
+    /// Panics if the rows are not contiguous in memory
+    #[must_use]
+    pub const fn buffer_flat_bytes(&self) -> &[u8] {
+        let buffer = self.buffer_flat();
+        unsafe { std::slice::from_raw_parts(buffer.as_ptr().cast(), std::mem::size_of_val(buffer)) }
+    }
+
+    /// Iterates over the primitives of all rows from top to bottom, skipping the row padding
+    #[must_use]
+    pub fn rows_flat(&self) -> impl ExactSizeIterator<Item = &[TP::Primitive]> + '_ {
+        self.0.rows_flat()
+    }
+
+    /// # Panics
+    /// Panics if the rows are not contiguous in memory. Use [`Self::primitive_rows_mut`] for strided channels.
     pub fn primitive_make_mut(&mut self) -> &mut [TP::Primitive] {
+        assert!(
+            self.0.is_contiguous(),
+            "Channel is not contiguous, use primitive_rows_mut() instead"
+        );
         unsafe {
             (self.0.vtable.make_mut)(&mut self.0);
             let len = self.len_flat();
@@ -276,6 +426,18 @@ impl<TP: RuntimePixelType> ImageChannel<TP> {
         }
     }
 
+    /// Like [`Self::rows_flat`], but ensures exclusive access to the buffer first
+    pub fn primitive_rows_mut(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = &mut [TP::Primitive]> + '_ {
+        unsafe { (self.0.vtable.make_mut)(&mut self.0) };
+        let channel = &self.0;
+        let len = channel.row_len_flat();
+        (0..channel.height.get()).map(move |y| unsafe {
+            std::slice::from_raw_parts_mut(channel.row_ptr(y).cast_mut(), len)
+        })
+    }
+
     #[must_use]
     pub fn into_vec_flat(self) -> Vec<TP::Primitive>
     where
@@ -291,7 +453,7 @@ impl<TP: RuntimePixelType> ImageChannel<TP> {
             std::mem::forget(self);
             result
         } else {
-            self.buffer_flat().to_vec()
+            self.0.to_vec_flat()
         }
     }
 
@@ -314,6 +476,49 @@ impl<TP: RuntimePixelType> ImageChannel<TP> {
     pub const fn pixel_elements(&self) -> NonZeroU8 {
         self.0.pixel_elements
     }
+
+    /// Distance between the start of two consecutive rows in `TP::Primitive`s (row pitch)
+    #[must_use]
+    pub const fn stride(&self) -> usize {
+        self.0.stride
+    }
+
+    /// `true`, if there is no padding between rows, so the whole channel can be accessed as one slice
+    #[must_use]
+    pub const fn is_contiguous(&self) -> bool {
+        self.0.is_contiguous()
+    }
+
+    /// Largest power of two in bytes, to which the start of every row is aligned
+    #[must_use]
+    pub fn alignment(&self) -> usize {
+        let row_offsets = if self.0.height.get() == 1 {
+            0
+        } else {
+            self.0.stride * size_of::<TP::Primitive>()
+        };
+        1 << (self.0.ptr.addr() | row_offsets).trailing_zeros()
+    }
+}
+
+impl<TP: RuntimePixelType> ImageChannel<TP>
+where
+    TP::Primitive: Into<f32> + Copy,
+{
+    /// Lossless conversion into a packed `f32` channel with the same pixel layout (e.g. `[half::f16; 3]` -> `[f32; 3]`)
+    #[must_use]
+    pub fn to_f32(&self) -> ImageChannel<TP::WithPrimitive<f32>> {
+        let data = self
+            .rows_flat()
+            .flat_map(|row| row.iter().map(|x| (*x).into()))
+            .collect();
+        ImageChannel(UnsafeImageChannel::new_vec(
+            data,
+            self.0.width,
+            self.0.height,
+            self.0.pixel_elements,
+        ))
+    }
 }
 
 impl<T: PixelTypePrimitive> ImageChannel<DynamicSize<T>> {
@@ -332,6 +537,21 @@ impl<T: PixelTypePrimitive> ImageChannel<DynamicSize<T>> {
             None
         }
     }
+
+    /// Takes ownership of a channel, which might have been created by another build of this crate (e.g. in a plugin)
+    ///
+    /// Like [`UnsafeImageChannel::try_import`], this can't detect channels of builds before the [`ImageChannelAbi`] was added.
+    ///
+    /// # Errors
+    /// Fails if the channel has a different [`ImageChannelAbi`]. It is left untouched and must be released by its creator.
+    ///
+    /// # Safety
+    /// See [`UnsafeImageChannel::try_import`]
+    pub unsafe fn try_import(
+        channel: NonNull<UnsafeImageChannel<T>>,
+    ) -> Result<Self, IncompatibleAbiError> {
+        unsafe { UnsafeImageChannel::try_import(channel) }.map(Self)
+    }
 }
 
 impl<TP: RuntimePixelType> Debug for ImageChannel<TP>
@@ -344,6 +564,7 @@ where
             .field("height", &self.0.height)
             .field("pixel", &std::any::type_name::<TP::Primitive>())
             .field("pixel_elements", &self.0.pixel_elements.get())
+            .field("stride", &self.0.stride)
             .finish()
     }
 }
@@ -351,22 +572,107 @@ where
 unsafe impl<TP: RuntimePixelType> Send for ImageChannel<TP> where TP::Primitive: Send {}
 unsafe impl<TP: RuntimePixelType> Sync for ImageChannel<TP> where TP::Primitive: Sync {}
 
+/// Returned by fallible constructors, if the length of the buffer doesn't match the dimensions.
+/// Gives back the `buffer`, so it can be reused
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Incompatible Buffer-Size: expected {} elements, got {actual}",
+    .expected.map_or_else(|| "more than usize::MAX".to_owned(), |x| x.to_string())
+)]
+pub struct IncompatibleBufferSizeError<TInput> {
+    pub buffer: TInput,
+    /// `None`, if the dimensions overflow `usize`
+    pub expected: Option<usize>,
+    pub actual: usize,
+}
+
+impl<TInput> IncompatibleBufferSizeError<TInput> {
+    pub(crate) fn check(
+        buffer: TInput,
+        expected: Option<usize>,
+        len: impl FnOnce(&TInput) -> usize,
+    ) -> Result<TInput, Self> {
+        let actual = len(&buffer);
+        if Some(actual) == expected {
+            Ok(buffer)
+        } else {
+            Err(Self {
+                buffer,
+                expected,
+                actual,
+            })
+        }
+    }
+}
+
+/// Incremented whenever the layout of [`UnsafeImageChannel`] or [`ImageChannelVTable`] changes
+pub const IMAGE_CHANNEL_ABI_VERSION: u32 = 1;
+
+/// Layout of [`UnsafeImageChannel`] and its vtable, which is compared before channels are exchanged between separately compiled libraries
+///
+/// It is the first field of [`ImageChannelVTable`], which is pointed to by the first field of [`UnsafeImageChannel`].
+/// This prefix stays the same in all future versions, so it can be read without knowing the rest of the layout.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageChannelAbi {
+    /// [`IMAGE_CHANNEL_ABI_VERSION`] of the build, which created the vtable
+    pub version: u32,
+    pub channel_size: u32,
+    pub channel_align: u32,
+    pub vtable_size: u32,
+    pub primitive_size: u32,
+    pub primitive_align: u32,
+    /// Distinguishes primitives of the same size, e.g. `u32` from `f32` (the character of `NumPy`'s type kind, e.g. `'f'`)
+    pub primitive_kind: u32,
+}
+
+impl ImageChannelAbi {
+    /// Layout of `UnsafeImageChannel<T>` in this build
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn of<T: PixelTypePrimitive>() -> Self {
+        Self {
+            version: IMAGE_CHANNEL_ABI_VERSION,
+            channel_size: size_of::<UnsafeImageChannel<T>>() as u32,
+            channel_align: align_of::<UnsafeImageChannel<T>>() as u32,
+            vtable_size: size_of::<ImageChannelVTable<T>>() as u32,
+            primitive_size: size_of::<T>() as u32,
+            primitive_align: align_of::<T>() as u32,
+            primitive_kind: T::ARRAY_KIND as u32,
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Channel was created by an incompatible build: expected {expected:?}, got {actual:?}")]
+pub struct IncompatibleAbiError {
+    pub expected: ImageChannelAbi,
+    pub actual: ImageChannelAbi,
+}
+
 /// `VTable` for [`ImageChannel`] and [`UnsafeImageChannel`]
 #[repr(C)]
 pub struct ImageChannelVTable<T: 'static> {
+    /// Must be [`ImageChannelAbi::of`] and stay the first field
+    pub abi: ImageChannelAbi,
     pub clone: unsafe extern "C" fn(&UnsafeImageChannel<T>) -> UnsafeImageChannel<T>,
     pub make_mut: unsafe extern "C" fn(&mut UnsafeImageChannel<T>),
     pub drop: unsafe extern "C" fn(&mut UnsafeImageChannel<T>),
 }
 
 /// This is only relevant when implementing a custom storage
-/// T is usually a `PixelTypePrimitive`, but it is not enforced by the type system
+/// T is usually a `PixelTypePrimitive`. The constructors of this crate require it, so the [`ImageChannelAbi`] can identify the primitive
 #[repr(C)]
 pub struct UnsafeImageChannel<T: 'static> {
+    /// Stays the first field, so [`UnsafeImageChannel::try_import`] can check the [`ImageChannelAbi`]
+    pub vtable: &'static ImageChannelVTable<T>,
     pub ptr: *const T,
     pub width: NonZeroU32,
     pub height: NonZeroU32,
-    pub vtable: &'static ImageChannelVTable<T>,
+    /// Distance between the start of two consecutive rows in `T`s. Is `width * pixel_elements` for packed buffers
+    pub stride: usize,
     // Has to be cleaned up by clear proc too
     pub data: *mut (),
     pub pixel_elements: NonZeroU8,
@@ -379,28 +685,97 @@ impl<T: 'static> UnsafeImageChannel<T> {
     ///
     /// # Safety
     /// The vtable must be able to cleanup the fields
+    /// `ptr` must be valid for reading `height` rows of `width * pixel_elements`, each `stride` `T`s apart
+    ///
+    /// # Panics
+    /// Panics if `stride` is smaller than `width * pixel_elements`
     pub unsafe fn new_with_vtable(
         ptr: *const T,
         width: NonZeroU32,
         height: NonZeroU32,
+        stride: usize,
         pixel_elements: NonZeroU8,
         vtable: &'static ImageChannelVTable<T>,
         data: *mut (),
     ) -> Self {
+        assert!(
+            stride >= calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
+            "Stride must be at least width * pixel_elements"
+        );
         UnsafeImageChannel {
+            vtable,
             ptr,
             width,
             height,
-            vtable,
+            stride,
             data,
             pixel_elements,
         }
     }
 
+    /// Takes ownership of a channel, which might have been created by another build of this crate (e.g. in a plugin)
+    ///
+    /// Only the vtable pointer and the [`ImageChannelAbi`] are read, before the layout is known to match.
+    /// Channels of builds before the [`ImageChannelAbi`] was added (where `ptr` is the first field) can't be detected and are misread.
+    ///
+    /// # Errors
+    /// Fails if the channel has a different [`ImageChannelAbi`]. It is left untouched and must be released by its creator.
+    ///
+    /// # Safety
+    /// `channel` must point to a valid channel, which isn't used afterwards if this succeeds
+    pub unsafe fn try_import(channel: NonNull<Self>) -> Result<Self, IncompatibleAbiError>
+    where
+        T: PixelTypePrimitive,
+    {
+        let actual = unsafe { channel.cast::<*const ImageChannelAbi>().read().read() };
+        let expected = ImageChannelAbi::of::<T>();
+        if actual != expected {
+            return Err(IncompatibleAbiError { expected, actual });
+        }
+        Ok(unsafe { channel.read() })
+    }
+
+    /// Only valid for contiguous channels
     pub(crate) const fn buffer_flat(&self) -> &[T] {
+        debug_assert!(self.is_contiguous());
         unsafe { std::slice::from_raw_parts(self.ptr, self.calc_len_flat()) }
     }
 
+    pub(crate) const fn is_contiguous(&self) -> bool {
+        self.height.get() == 1 || self.stride == self.row_len_flat()
+    }
+
+    /// Number of `T` in a row without padding
+    pub(crate) const fn row_len_flat(&self) -> usize {
+        calc_pixel_len_flat(self.width, NonZeroU32::MIN, self.pixel_elements)
+    }
+
+    pub(crate) const fn row_ptr(&self, y: u32) -> *const T {
+        unsafe { self.ptr.add(y as usize * self.stride) }
+    }
+
+    pub(crate) fn rows_flat(&self) -> impl ExactSizeIterator<Item = &[T]> + '_ {
+        let len = self.row_len_flat();
+        (0..self.height.get())
+            .map(move |y| unsafe { std::slice::from_raw_parts(self.row_ptr(y), len) })
+    }
+
+    /// Copies all rows into a packed `Vec`
+    pub(crate) fn to_vec_flat(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        if self.is_contiguous() {
+            self.buffer_flat().to_vec()
+        } else {
+            let mut result = Vec::with_capacity(self.calc_len_flat());
+            for row in self.rows_flat() {
+                result.extend_from_slice(row);
+            }
+            result
+        }
+    }
+
     pub(crate) const fn calc_len_packed(&self) -> usize {
         calc_pixel_len_packed(self.width, self.height)
     }
@@ -425,6 +800,25 @@ pub(crate) const fn calc_pixel_len_flat(
     calc_pixel_len_packed(width, height) * pixel_elements.get() as usize
 }
 
+/// Like [`calc_pixel_len_packed`], but `None` on overflow, for dimensions which aren't backed by a buffer yet
+pub(crate) const fn checked_calc_pixel_len_packed(
+    width: NonZeroU32,
+    height: NonZeroU32,
+) -> Option<usize> {
+    (width.get() as usize).checked_mul(height.get() as usize)
+}
+/// Like [`calc_pixel_len_flat`], but `None` on overflow, for dimensions which aren't backed by a buffer yet
+pub(crate) const fn checked_calc_pixel_len_flat(
+    width: NonZeroU32,
+    height: NonZeroU32,
+    pixel_elements: NonZeroU8,
+) -> Option<usize> {
+    match checked_calc_pixel_len_packed(width, height) {
+        Some(len) => len.checked_mul(pixel_elements.get() as usize),
+        None => None,
+    }
+}
+
 impl<T> Drop for UnsafeImageChannel<T> {
     fn drop(&mut self) {
         if self.ptr as usize != 0 {
@@ -467,6 +861,38 @@ mod tests {
         assert!(dyn_u8_ch.try_cast::<[u8; 4]>().is_none());
     }
 
+    #[test]
+    fn try_new_vec_returns_buffer_on_size_mismatch() {
+        let raw = vec![[0u8; 3]; 3];
+        let pointer = raw.as_ptr();
+        let size = 2.try_into().unwrap();
+        let err = ImageChannel::<[u8; 3]>::try_new_vec(raw, size, size).unwrap_err();
+        assert_eq!((err.expected, err.actual), (Some(4), 3));
+        assert_eq!(err.buffer.as_ptr(), pointer);
+    }
+
+    #[test]
+    fn try_new_vec_reports_overflowing_dimensions() {
+        let max = NonZeroU32::MAX;
+        let err =
+            UnsafeImageChannel::try_new_vec(vec![0u8; 4], max, max, NonZeroU8::new(4).unwrap())
+                .err()
+                .unwrap();
+        assert_eq!((err.expected, err.actual), (None, 4));
+    }
+
+    #[test]
+    fn try_new_arc_returns_buffer_on_size_mismatch() {
+        let raw = Arc::<[u16]>::from([1u16, 2].as_slice());
+        let err = ImageChannel::<u16>::try_new_arc(raw.clone(), NonZeroU32::MIN, NonZeroU32::MIN)
+            .unwrap_err();
+        assert!(Arc::ptr_eq(&err.buffer, &raw));
+        assert_eq!(
+            err.to_string(),
+            "Incompatible Buffer-Size: expected 1 elements, got 2"
+        );
+    }
+
     #[test]
     fn miri_create_and_clear_vec_image_channel() {
         let size = 2.try_into().unwrap();
@@ -610,4 +1036,126 @@ mod tests {
         let image = ImageChannel::new_vec(vec![42u16], NonZeroU32::MIN, NonZeroU32::MIN);
         assert_eq!(image.buffer_flat_bytes(), &[42u8, 0u8]);
     }
+
+    /// Channel over a padded `Vec<u8>`, as it could be returned by a camera SDK
+    fn strided_u8_channel(
+        padded: Vec<u8>,
+        width: u32,
+        height: u32,
+        stride: usize,
+    ) -> ImageChannel<u8> {
+        unsafe extern "C" fn make_mut(_image: &mut UnsafeImageChannel<u8>) {}
+        unsafe extern "C" fn drop(image: &mut UnsafeImageChannel<u8>) {
+            unsafe { std::mem::drop(Box::from_raw(image.data.cast::<Vec<u8>>())) };
+        }
+        static VTABLE: ImageChannelVTable<u8> = ImageChannelVTable {
+            abi: ImageChannelAbi::of::<u8>(),
+            clone: crate::arc::clone_slice_into_arc_channel,
+            make_mut,
+            drop,
+        };
+        let ptr = padded.as_ptr();
+        let data = Box::into_raw(Box::new(padded)).cast();
+        unsafe {
+            ImageChannel::new_with_vtable(
+                ptr,
+                width.try_into().unwrap(),
+                height.try_into().unwrap(),
+                stride,
+                &VTABLE,
+                data,
+            )
+        }
+    }
+
+    #[test]
+    fn miri_strided_channel_rows() {
+        let channel = strided_u8_channel(vec![1, 2, 0, 0, 3, 4, 0, 0], 2, 2, 4);
+        assert!(!channel.is_contiguous());
+        assert_eq!(channel.stride(), 4);
+        assert_eq!(channel.try_buffer(), None);
+        assert_eq!(channel.row(1), Some([3u8, 4].as_slice()));
+        assert_eq!(channel.row(2), None);
+        assert_eq!(
+            channel.rows().collect::<Vec<_>>(),
+            [[1u8, 2].as_slice(), &[3, 4]]
+        );
+
+        let two = NonZeroU32::new(2).unwrap();
+        let packed = ImageChannel::<u8>::new_vec(vec![1, 2, 3, 4], two, two);
+        assert_eq!(channel, packed, "Padding is ignored when comparing");
+        assert_eq!(channel.clone().buffer(), &[1, 2, 3, 4], "Clones are packed");
+        assert_eq!(channel.into_vec(), vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn miri_strided_channel_rows_mut() {
+        let mut channel = strided_u8_channel(vec![1, 2, 9, 3, 4, 9], 2, 2, 3);
+        for row in channel.rows_mut() {
+            row[0] = 0;
+        }
+        assert_eq!(
+            channel.rows_flat().flatten().copied().collect::<Vec<_>>(),
+            [0, 2, 0, 4]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "not contiguous")]
+    fn strided_channel_buffer_panics() {
+        let channel = strided_u8_channel(vec![1, 2, 0, 3, 4, 0], 2, 2, 3);
+        let _ = channel.buffer();
+    }
+
+    #[test]
+    fn single_row_with_padding_is_contiguous() {
+        let channel = strided_u8_channel(vec![1, 2, 0], 2, 1, 3);
+        assert!(channel.is_contiguous());
+        assert_eq!(channel.buffer(), &[1, 2]);
+    }
+
+    #[test]
+    fn miri_try_import_checks_abi() {
+        unsafe extern "C" fn make_mut(_image: &mut UnsafeImageChannel<u8>) {}
+        unsafe extern "C" fn drop(_image: &mut UnsafeImageChannel<u8>) {}
+        static FOREIGN: ImageChannelVTable<u8> = ImageChannelVTable {
+            abi: ImageChannelAbi {
+                version: IMAGE_CHANNEL_ABI_VERSION + 1,
+                ..ImageChannelAbi::of::<u8>()
+            },
+            clone: crate::arc::clone_slice_into_arc_channel,
+            make_mut,
+            drop,
+        };
+        let channel = UnsafeImageChannel::new_vec(
+            vec![1u8, 2],
+            NonZeroU32::MIN,
+            NonZeroU32::MIN,
+            NonZeroU8::new(2).unwrap(),
+        );
+        let mut channel = std::mem::ManuallyDrop::new(channel);
+        let imported =
+            unsafe { ImageChannel::<DynamicSize<u8>>::try_import(NonNull::from(&mut *channel)) };
+        assert_eq!(imported.unwrap().buffer_flat(), &[1, 2]);
+
+        let data = [3u8];
+        let mut foreign = unsafe {
+            UnsafeImageChannel::new_with_vtable(
+                data.as_ptr(),
+                NonZeroU32::MIN,
+                NonZeroU32::MIN,
+                1,
+                NonZeroU8::MIN,
+                &FOREIGN,
+                std::ptr::null_mut(),
+            )
+        };
+        let error = unsafe { UnsafeImageChannel::try_import(NonNull::from(&mut foreign)) }
+            .err()
+            .unwrap();
+        assert_eq!(error.expected, ImageChannelAbi::of::<u8>());
+        assert_eq!(error.actual, FOREIGN.abi);
+        // Same size and alignment, but a different primitive
+        assert_ne!(ImageChannelAbi::of::<u32>(), ImageChannelAbi::of::<f32>());
+    }
 }
@@ -0,0 +1,388 @@
+//! Opt-in colour model conversions for interleaved (`Image<[T; 3], 1>`) and planar (`Image<T, 3>`) images.
+//!
+//! The crate attaches no meaning to channels, so these conversions simply assume the channel order of their name (e.g. RGB).
+//! Values are expected in the nominal range, which is `0..=MAX` for integers and `0.0..=1.0` for floats.
+//! HSV and YCbCr use the same range for all channels, so a hue of `MAX` is a full turn.
+use crate::{ChannelLayout, ChannelSemantics, Image, ImageChannels, Metadata, PixelTypePrimitive};
+
+/// Weights of R, G and B for the luma
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LumaCoefficients {
+    /// SDTV (e.g. JPEG)
+    #[default]
+    Bt601,
+    /// HDTV
+    Bt709,
+}
+
+impl LumaCoefficients {
+    fn weights(self) -> [f64; 3] {
+        match self {
+            LumaCoefficients::Bt601 => [0.299, 0.587, 0.114],
+            LumaCoefficients::Bt709 => [0.2126, 0.7152, 0.0722],
+        }
+    }
+
+    fn luma(self, rgb: [f64; 3]) -> f64 {
+        self.weights().iter().zip(rgb).map(|(w, x)| w * x).sum()
+    }
+}
+
+macro_rules! impl_color_conversions {
+    ($image:ty, $map:ident, $from_pixels:ident) => {
+        impl<T: PixelTypePrimitive> $image {
+            #[must_use]
+            pub fn rgb_to_gray(&self, coefficients: LumaCoefficients) -> Image<T, 1> {
+                let data = $map(self, |rgb| {
+                    T::from_unit_f64(coefficients.luma(rgb.map(T::to_unit_f64)))
+                });
+                Image::new_vec(data, self.width(), self.height())
+            }
+
+            #[must_use]
+            pub fn rgb_to_hsv(&self) -> Self {
+                self.map_unit(rgb_to_hsv)
+            }
+
+            #[must_use]
+            pub fn hsv_to_rgb(&self) -> Self {
+                self.map_unit(hsv_to_rgb)
+            }
+
+            /// Full range BT.601 (as used by JPEG)
+            #[must_use]
+            pub fn rgb_to_ycbcr(&self) -> Self {
+                self.map_unit(rgb_to_ycbcr)
+            }
+
+            /// Full range BT.601 (as used by JPEG)
+            #[must_use]
+            pub fn ycbcr_to_rgb(&self) -> Self {
+                self.map_unit(ycbcr_to_rgb)
+            }
+
+            fn map_unit(&self, f: fn([f64; 3]) -> [f64; 3]) -> Self {
+                let (width, height) = self.dimensions();
+                let pixels = $map(self, |pixel| {
+                    f(pixel.map(T::to_unit_f64)).map(T::from_unit_f64)
+                });
+                $from_pixels(pixels, width, height)
+            }
+        }
+    };
+}
+
+impl_color_conversions!(Image<[T; 3], 1>, map_interleaved, interleaved_from_pixels);
+impl_color_conversions!(Image<T, 3>, map_planar, planar_from_pixels);
+
+impl<T: PixelTypePrimitive> Image<[T; 3], 1> {
+    /// Swaps the first and the last element of each pixel in place (see [`crate::ImageChannel::make_mut`]) and the layout of the [`ChannelSemantics`]
+    #[must_use]
+    pub fn rgb_to_bgr(mut self) -> Self {
+        for [row] in self.rows_mut() {
+            for pixel in row {
+                pixel.swap(0, 2);
+            }
+        }
+        swap_red_blue(self.metadata_mut());
+        self
+    }
+
+    /// See [`Image::rgb_to_bgr`]
+    #[must_use]
+    pub fn bgr_to_rgb(self) -> Self {
+        self.rgb_to_bgr()
+    }
+}
+
+impl<T: PixelTypePrimitive> Image<T, 3> {
+    /// Swaps the first and the last channel without copying pixels and the layout of the [`ChannelSemantics`]
+    #[must_use]
+    pub fn rgb_to_bgr(self) -> Self {
+        let ImageChannels([r, g, b], mut metadata) = self;
+        swap_red_blue(&mut metadata);
+        ImageChannels([b, g, r], metadata)
+    }
+
+    /// See [`Image::rgb_to_bgr`]
+    #[must_use]
+    pub fn bgr_to_rgb(self) -> Self {
+        self.rgb_to_bgr()
+    }
+}
+
+fn swap_red_blue(metadata: &mut Metadata) {
+    if let Some(semantics) = metadata.get_mut::<ChannelSemantics>() {
+        semantics.layout = match semantics.layout {
+            ChannelLayout::Rgb => ChannelLayout::Bgr,
+            ChannelLayout::Bgr => ChannelLayout::Rgb,
+            ChannelLayout::Rgba => ChannelLayout::Bgra,
+            ChannelLayout::Bgra => ChannelLayout::Rgba,
+            layout => layout,
+        };
+    }
+}
+
+impl<T: PixelTypePrimitive> Image<T, 1> {
+    #[must_use]
+    pub fn gray_to_rgb(&self) -> Image<[T; 3], 1> {
+        let data = self
+            .rows()
+            .flat_map(|[row]| row.iter().map(|x| [*x; 3]))
+            .collect();
+        Image::new_vec(data, self.width(), self.height())
+    }
+
+    #[must_use]
+    pub fn gray_to_rgb_planar(&self) -> Image<T, 3> {
+        let data = [(); 3]
+            .iter()
+            .flat_map(|()| self.rows().flat_map(|[row]| row.iter().copied()))
+            .collect();
+        Image::new_vec(data, self.width(), self.height())
+    }
+}
+
+fn map_interleaved<T: PixelTypePrimitive, U>(
+    image: &Image<[T; 3], 1>,
+    f: impl Fn([T; 3]) -> U,
+) -> Vec<U> {
+    image
+        .rows()
+        .flat_map(|[row]| row.iter().copied().map(&f))
+        .collect()
+}
+
+fn map_planar<T: PixelTypePrimitive, U>(image: &Image<T, 3>, f: impl Fn([T; 3]) -> U) -> Vec<U> {
+    image
+        .rows()
+        .flat_map(|[r, g, b]| r.iter().zip(g).zip(b).map(|((r, g), b)| f([*r, *g, *b])))
+        .collect()
+}
+
+fn interleaved_from_pixels<T: PixelTypePrimitive>(
+    pixels: Vec<[T; 3]>,
+    width: std::num::NonZeroU32,
+    height: std::num::NonZeroU32,
+) -> Image<[T; 3], 1> {
+    Image::new_vec(pixels, width, height)
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn planar_from_pixels<T: PixelTypePrimitive>(
+    pixels: Vec<[T; 3]>,
+    width: std::num::NonZeroU32,
+    height: std::num::NonZeroU32,
+) -> Image<T, 3> {
+    let data = (0..3)
+        .flat_map(|c| pixels.iter().map(move |pixel| pixel[c]))
+        .collect();
+    Image::new_vec(data, width, height)
+}
+
+#[allow(clippy::float_cmp, clippy::many_single_char_names)]
+fn rgb_to_hsv([r, g, b]: [f64; 3]) -> [f64; 3] {
+    let max = r.max(g).max(b);
+    let delta = max - r.min(g).min(b);
+    let hue = if delta == 0.0 {
+        0.0
+    } else if max == r {
+        ((g - b) / delta).rem_euclid(6.0)
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    };
+    let saturation = if max == 0.0 { 0.0 } else { delta / max };
+    [hue / 6.0, saturation, max]
+}
+
+#[allow(clippy::many_single_char_names)]
+fn hsv_to_rgb([h, s, v]: [f64; 3]) -> [f64; 3] {
+    let h = h.rem_euclid(1.0) * 6.0;
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let m = v - c;
+    let [r, g, b] = match h.floor() {
+        0.0 => [c, x, 0.0],
+        1.0 => [x, c, 0.0],
+        2.0 => [0.0, c, x],
+        3.0 => [0.0, x, c],
+        4.0 => [x, 0.0, c],
+        _ => [c, 0.0, x],
+    };
+    [r + m, g + m, b + m]
+}
+
+fn rgb_to_ycbcr(rgb: [f64; 3]) -> [f64; 3] {
+    let [r, _, b] = rgb;
+    let y = LumaCoefficients::Bt601.luma(rgb);
+    [y, 0.5 + (b - y) / 1.772, 0.5 + (r - y) / 1.402]
+}
+
+fn ycbcr_to_rgb([y, cb, cr]: [f64; 3]) -> [f64; 3] {
+    let cb = cb - 0.5;
+    let cr = cr - 0.5;
+    [
+        y + 1.402 * cr,
+        y - 0.344_136 * cb - 0.714_136 * cr,
+        y + 1.772 * cb,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+    use crate::TransferFunction;
+
+    fn interleaved<T: PixelTypePrimitive>(pixels: Vec<[T; 3]>) -> Image<[T; 3], 1> {
+        let width = NonZeroU32::new(u32::try_from(pixels.len()).unwrap()).unwrap();
+        Image::new_vec(pixels, width, NonZeroU32::MIN)
+    }
+
+    #[test]
+    fn rgb_to_bgr() {
+        let image = interleaved(vec![[1u8, 2, 3], [4, 5, 6]]);
+        let ptr = image.buffer().as_ptr();
+        let swapped = image.clone().rgb_to_bgr();
+        assert_eq!(swapped.buffer(), &[[3, 2, 1], [6, 5, 4]]);
+        assert_eq!(image.buffer(), &[[1, 2, 3], [4, 5, 6]]);
+        drop(image);
+        let swapped_ptr = swapped.buffer().as_ptr();
+        assert_ne!(swapped_ptr, ptr);
+        assert_eq!(swapped.bgr_to_rgb().buffer().as_ptr(), swapped_ptr);
+
+        let planar = Image::<u8, 3>::new_vec(
+            vec![1, 4, 2, 5, 3, 6],
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let ptr = planar.buffers()[2].as_ptr();
+        let swapped = planar.bgr_to_rgb();
+        assert_eq!(swapped.buffers(), [[3, 6], [2, 5], [1, 4]]);
+        assert_eq!(swapped.buffers()[0].as_ptr(), ptr);
+    }
+
+    #[test]
+    fn rgb_to_bgr_swaps_semantics() {
+        let mut image = interleaved(vec![[1u8, 2, 3]]);
+        image.metadata_mut().insert(
+            ChannelSemantics::new(ChannelLayout::Rgb).with_transfer(TransferFunction::Srgb),
+        );
+        let image = image.rgb_to_bgr();
+        assert_eq!(
+            image.metadata().get::<ChannelSemantics>(),
+            Some(&ChannelSemantics::new(ChannelLayout::Bgr).with_transfer(TransferFunction::Srgb))
+        );
+
+        let mut planar = Image::<u8, 3>::from_interleaved(&image);
+        planar
+            .metadata_mut()
+            .insert(ChannelSemantics::new(ChannelLayout::Bgr));
+        let planar = planar.bgr_to_rgb();
+        assert_eq!(
+            planar.metadata().get::<ChannelSemantics>().unwrap().layout,
+            ChannelLayout::Rgb
+        );
+        assert!(
+            interleaved(vec![[1u8, 2, 3]])
+                .rgb_to_bgr()
+                .metadata()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn rgb_to_gray() {
+        let image = interleaved(vec![[255u8, 255, 255], [255, 0, 0]]);
+        assert_eq!(
+            image.rgb_to_gray(LumaCoefficients::Bt601).buffer(),
+            &[255, 76]
+        );
+        assert_eq!(
+            image.rgb_to_gray(LumaCoefficients::Bt709).buffer(),
+            &[255, 54]
+        );
+
+        let image = interleaved(vec![[1.0f32, 1.0, 1.0]]);
+        assert_eq!(
+            image.rgb_to_gray(LumaCoefficients::default()).buffer(),
+            &[1.0]
+        );
+        let planar = Image::<i16, 3>::new_vec(
+            vec![i16::MAX, i16::MAX, i16::MAX],
+            NonZeroU32::MIN,
+            NonZeroU32::MIN,
+        );
+        assert_eq!(
+            planar.rgb_to_gray(LumaCoefficients::Bt709).buffer(),
+            &[i16::MAX]
+        );
+    }
+
+    #[test]
+    fn gray_to_rgb() {
+        let gray =
+            Image::<u16, 1>::new_vec(vec![1, 2], NonZeroU32::new(2).unwrap(), NonZeroU32::MIN);
+        assert_eq!(gray.gray_to_rgb().buffer(), &[[1; 3], [2; 3]]);
+        assert_eq!(gray.gray_to_rgb_planar().buffers(), [[1, 2]; 3]);
+    }
+
+    #[test]
+    fn hsv() {
+        let image = interleaved(vec![[1.0f64, 0.0, 0.0], [0.0, 0.5, 0.5], [0.2, 0.2, 0.2]]);
+        let hsv = image.rgb_to_hsv();
+        assert_eq!(
+            hsv.buffer(),
+            &[[0.0, 1.0, 1.0], [0.5, 1.0, 0.5], [0.0, 0.0, 0.2]]
+        );
+        assert_eq!(hsv.hsv_to_rgb(), image);
+    }
+
+    #[test]
+    fn hsv_roundtrip_u8() {
+        let image = interleaved(vec![
+            [200u8, 30, 60],
+            [10, 120, 240],
+            [0, 0, 0],
+            [255, 255, 0],
+        ]);
+        let planar = Image::<u8, 3>::from_interleaved(&image);
+        let hsv = image.rgb_to_hsv();
+        assert_eq!(Image::<u8, 3>::from_interleaved(&hsv), planar.rgb_to_hsv());
+        for (a, b) in hsv.hsv_to_rgb().buffer().iter().zip(image.buffer()) {
+            for (a, b) in a.iter().zip(b) {
+                // Hue has 256 steps for a full turn
+                assert!(a.abs_diff(*b) <= 4, "{a} != {b}");
+            }
+        }
+    }
+
+    #[test]
+    fn ycbcr() {
+        let image = interleaved(vec![[255u8, 0, 0], [128, 128, 128]]);
+        let ycbcr = image.rgb_to_ycbcr();
+        assert_eq!(ycbcr.buffer(), &[[76, 84, 255], [128, 128, 128]]);
+        for (a, b) in ycbcr.ycbcr_to_rgb().buffer().iter().zip(image.buffer()) {
+            for (a, b) in a.iter().zip(b) {
+                assert!(a.abs_diff(*b) <= 1, "{a} != {b}");
+            }
+        }
+
+        let planar =
+            Image::<f32, 3>::new_vec(vec![0.1, 0.5, 0.9], NonZeroU32::MIN, NonZeroU32::MIN);
+        let roundtrip = planar.rgb_to_ycbcr().ycbcr_to_rgb();
+        for (a, b) in roundtrip
+            .rows()
+            .next()
+            .unwrap()
+            .iter()
+            .zip(planar.rows().next().unwrap())
+        {
+            assert!((a[0] - b[0]).abs() < 1e-5);
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Conversions between primitives (e.g. `u16` -> `f32`) with an explicit policy for the value range
+use crate::{
+    BorrowableImageChannel, DynamicImageChannel, Image, ImageChannel, ImageChannels,
+    PixelTypePrimitive, channel::UnsafeImageChannel, dynamic::match_dynamic_channel,
+    pixel::RuntimePixelType,
+};
+
+/// How values are mapped, when the primitive of a channel changes
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DepthScaling {
+    /// Maps the nominal range of the source onto the one of the target,
+    /// which is `0..=MAX` for integers and `0.0..=1.0` for floats (e.g. `u8::MAX` -> `1.0f32` -> `u16::MAX`)
+    Normalize,
+    /// Keeps the numeric value like `as` does, so fractions are truncated and out of range values saturate
+    Cast,
+    /// Keeps the numeric value, rounds to the nearest integer and saturates out of range values
+    Saturate,
+    /// Computes `value * scale + offset`, which is rounded and saturated like [`DepthScaling::Saturate`]
+    Custom { scale: f64, offset: f64 },
+}
+
+impl DepthScaling {
+    fn convert<S: PixelTypePrimitive, D: PixelTypePrimitive>(self, value: S) -> D {
+        match self {
+            DepthScaling::Normalize => D::from_unit_f64(value.to_unit_f64()),
+            DepthScaling::Cast => D::from_f64(value.to_f64()),
+            DepthScaling::Saturate => D::from_f64_rounded(value.to_f64()),
+            DepthScaling::Custom { scale, offset } => {
+                D::from_f64_rounded(value.to_f64().mul_add(scale, offset))
+            }
+        }
+    }
+}
+
+impl<TP: RuntimePixelType> ImageChannel<TP> {
+    /// Converts every primitive into `P` and keeps the pixel layout (e.g. `[u16; 3]` -> `[f32; 3]`)
+    #[must_use]
+    pub fn convert_depth<P: PixelTypePrimitive>(
+        &self,
+        scaling: DepthScaling,
+    ) -> ImageChannel<TP::WithPrimitive<P>> {
+        let data = self
+            .rows_flat()
+            .flat_map(|row| row.iter().map(|x| scaling.convert(*x)))
+            .collect();
+        ImageChannel(UnsafeImageChannel::new_vec(
+            data,
+            self.0.width,
+            self.0.height,
+            self.0.pixel_elements,
+        ))
+    }
+}
+
+impl<T: BorrowableImageChannel, const CHANNELS: usize> ImageChannels<[T; CHANNELS]> {
+    /// Converts the primitives of all channels into `P` (e.g. `Image<u8, 3>` -> `Image<u16, 3>`)
+    #[must_use]
+    pub fn convert_depth<P: PixelTypePrimitive>(
+        &self,
+        scaling: DepthScaling,
+    ) -> Image<<T::Pixel as RuntimePixelType>::WithPrimitive<P>, CHANNELS> {
+        ImageChannels(
+            self.0
+                .each_ref()
+                .map(|channel| channel.borrow().convert_depth(scaling)),
+            self.1.clone(),
+        )
+    }
+}
+
+impl DynamicImageChannel {
+    /// Converts every primitive into `P` and keeps the number of pixel elements
+    #[must_use]
+    pub fn convert_depth<P: PixelTypePrimitive>(&self, scaling: DepthScaling) -> Self {
+        match_dynamic_channel!(self, x => P::into_runtime_channel(x.convert_depth(scaling)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::DepthScaling;
+    use crate::{DynamicImageChannel, Image, ImageChannel};
+
+    fn channel<T: crate::PixelTypePrimitive>(data: Vec<T>) -> ImageChannel<T> {
+        let width = NonZeroU32::new(u32::try_from(data.len()).unwrap()).unwrap();
+        ImageChannel::new_vec(data, width, NonZeroU32::MIN)
+    }
+
+    #[test]
+    fn normalize() {
+        let source = channel(vec![0u16, 32768, u16::MAX]);
+        let converted = source.convert_depth::<f32>(DepthScaling::Normalize);
+        assert_eq!(converted.buffer(), &[0.0, 32768.0 / 65535.0, 1.0]);
+        let converted = source.convert_depth::<u8>(DepthScaling::Normalize);
+        assert_eq!(converted.buffer(), &[0, 128, u8::MAX]);
+        let converted = channel(vec![-0.5f64, 2.0]).convert_depth::<u8>(DepthScaling::Normalize);
+        assert_eq!(converted.buffer(), &[0, u8::MAX]);
+    }
+
+    #[test]
+    fn cast_truncates_and_saturates() {
+        let converted = channel(vec![1.9f32, -1.0, 300.0]).convert_depth::<u8>(DepthScaling::Cast);
+        assert_eq!(converted.buffer(), &[1, 0, u8::MAX]);
+        let converted = channel(vec![-3i16, 1000]).convert_depth::<i8>(DepthScaling::Cast);
+        assert_eq!(converted.buffer(), &[-3, i8::MAX]);
+    }
+
+    #[test]
+    fn saturate_rounds() {
+        let converted =
+            channel(vec![1.5f32, 2.4, -7.0, 1e9]).convert_depth::<i16>(DepthScaling::Saturate);
+        assert_eq!(converted.buffer(), &[2, 2, -7, i16::MAX]);
+        let converted = channel(vec![3u8]).convert_depth::<f64>(DepthScaling::Saturate);
+        assert_eq!(converted.buffer(), &[3.0]);
+    }
+
+    #[test]
+    fn custom_scale_and_offset() {
+        let scaling = DepthScaling::Custom {
+            scale: 1.0 / 16.0,
+            offset: 1.0,
+        };
+        let converted = channel(vec![0u16, 160, 8000]).convert_depth::<u8>(scaling);
+        assert_eq!(converted.buffer(), &[1, 11, u8::MAX]);
+    }
+
+    #[test]
+    fn image_keeps_pixel_layout() {
+        let image = Image::<[u8; 2], 2>::new_vec(
+            vec![[0, 255], [255, 0]],
+            NonZeroU32::MIN,
+            NonZeroU32::MIN,
+        );
+        let converted: Image<[u16; 2], 2> = image.convert_depth(DepthScaling::Normalize);
+        assert_eq!(converted.buffers(), [[[0, 65535]], [[65535, 0]]]);
+    }
+
+    #[test]
+    fn dynamic_channel() {
+        let channel: DynamicImageChannel = channel(vec![10u8, 20]).into();
+        let converted = channel.convert_depth::<i32>(DepthScaling::Cast);
+        let DynamicImageChannel::I32(converted) = converted else {
+            panic!("Expected i32, got {converted:?}")
+        };
+        assert_eq!(converted.buffer_flat(), &[10, 20]);
+        assert_eq!(converted.pixel_elements(), channel.pixel_elements());
+    }
+}
@@ -0,0 +1,429 @@
+//! Exchange of CPU tensors via [DLPack](https://dmlc.github.io/dlpack/latest/) (`DLManagedTensor` of version 0.8)
+//!
+//! Channels are described as `height × width × pixel_elements` and images as `channels × height × width` tensor.
+//! Neither export nor import copies pixels, unless the planes of an image are not stored in one allocation.
+use std::{
+    ffi::c_void,
+    num::{NonZeroU8, NonZeroU32},
+    ptr::NonNull,
+    sync::Arc,
+};
+
+use crate::{
+    DynamicSize, Image, ImageChannel, PixelTypePrimitive, channel::UnsafeImageChannel,
+    pixel::RuntimePixelType, shared_vec::plane_stride,
+};
+
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DLDeviceType(pub i32);
+
+impl DLDeviceType {
+    pub const CPU: Self = Self(1);
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DLDevice {
+    pub device_type: DLDeviceType,
+    pub device_id: i32,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DLDataType {
+    /// `0` for signed integers, `1` for unsigned integers, `2` for floats and `4` for bfloat
+    pub code: u8,
+    pub bits: u8,
+    pub lanes: u16,
+}
+
+impl DLDataType {
+    fn of<T: PixelTypePrimitive>() -> Self {
+        let code = match T::ARRAY_KIND {
+            'i' => 0,
+            'u' => 1,
+            'f' => 2,
+            _ => 4,
+        };
+        #[allow(clippy::cast_possible_truncation)]
+        let bits = (size_of::<T>() * 8) as u8;
+        Self {
+            code,
+            bits,
+            lanes: 1,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLTensor {
+    pub data: *mut c_void,
+    pub device: DLDevice,
+    pub ndim: i32,
+    pub dtype: DLDataType,
+    pub shape: *mut i64,
+    /// In elements. NULL for C-contiguous tensors
+    pub strides: *mut i64,
+    /// In bytes
+    pub byte_offset: u64,
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct DLManagedTensor {
+    pub dl_tensor: DLTensor,
+    pub manager_ctx: *mut c_void,
+    pub deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Cannot import the DLPack tensor: {reason}")]
+#[non_exhaustive]
+pub struct IncompatibleTensorError {
+    /// Still owned by the caller
+    pub tensor: NonNull<DLManagedTensor>,
+    pub reason: IncompatibleTensorErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum IncompatibleTensorErrorReason {
+    #[error("Only CPU tensors are supported, got device type {device_type:?}")]
+    UnsupportedDevice { device_type: DLDeviceType },
+    #[error("Expected {expected:?}, got {actual:?}")]
+    IncompatibleDataType {
+        expected: DLDataType,
+        actual: DLDataType,
+    },
+    /// Height and width must be within `1..=u32::MAX` and pixel elements within `1..=255`
+    #[error("Tensors must have the shape height × width (× pixel_elements), got {shape:?}")]
+    InvalidShape { shape: Vec<i64> },
+    /// Pixels must be packed within rows and rows must not overlap
+    #[error("Unsupported strides {strides:?}")]
+    UnsupportedStrides { strides: Vec<i64> },
+    #[error("Data is not aligned")]
+    Misaligned,
+}
+
+/// Owns the exported pixels. `tensor` is the first field, so the deleter can cast it back.
+#[repr(C)]
+struct Export<TOwner> {
+    tensor: DLManagedTensor,
+    shape: [i64; 3],
+    strides: [i64; 3],
+    _owner: TOwner,
+}
+
+unsafe extern "C" fn delete_export<TOwner>(tensor: *mut DLManagedTensor) {
+    drop(unsafe { Box::from_raw(tensor.cast::<Export<TOwner>>()) });
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn export<T: PixelTypePrimitive, TOwner>(
+    data: *const T,
+    shape: [usize; 3],
+    strides: [usize; 3],
+    owner: TOwner,
+) -> NonNull<DLManagedTensor> {
+    let mut export = Box::new(Export {
+        tensor: DLManagedTensor {
+            dl_tensor: DLTensor {
+                data: data.cast_mut().cast(),
+                device: DLDevice {
+                    device_type: DLDeviceType::CPU,
+                    device_id: 0,
+                },
+                ndim: 3,
+                dtype: DLDataType::of::<T>(),
+                shape: std::ptr::null_mut(),
+                strides: std::ptr::null_mut(),
+                byte_offset: 0,
+            },
+            manager_ctx: std::ptr::null_mut(),
+            deleter: Some(delete_export::<TOwner>),
+        },
+        shape: shape.map(|x| x as i64),
+        strides: strides.map(|x| x as i64),
+        _owner: owner,
+    });
+    export.tensor.dl_tensor.shape = export.shape.as_mut_ptr();
+    export.tensor.dl_tensor.strides = export.strides.as_mut_ptr();
+    NonNull::from(Box::leak(export)).cast()
+}
+
+impl<TP: RuntimePixelType> ImageChannel<TP> {
+    /// Exports the channel as `height × width × pixel_elements` tensor. Its deleter drops the channel.
+    ///
+    /// Consumers may write to the tensor, so shared pixels are copied first (see [`ImageChannel::make_mut`]).
+    #[must_use]
+    pub fn into_dlpack(mut self) -> NonNull<DLManagedTensor> {
+        unsafe { (self.0.vtable.make_mut)(&mut self.0) };
+        let pixel_elements = usize::from(self.pixel_elements().get());
+        export(
+            self.0.ptr,
+            [
+                self.height().get() as usize,
+                self.width().get() as usize,
+                pixel_elements,
+            ],
+            [self.stride(), pixel_elements, 1],
+            self,
+        )
+    }
+}
+
+impl<T: PixelTypePrimitive, const CHANNELS: usize> Image<T, CHANNELS> {
+    /// Exports the image as `channels × height × width` tensor. Its deleter drops the image.
+    ///
+    /// Consumers may write to the tensor, so shared pixels are copied first (see [`ImageChannel::make_mut`]).
+    /// Channels, which are not stored in one allocation with the same distance (see [`Image::new_vec`]), are copied into one.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn into_dlpack(mut self) -> NonNull<DLManagedTensor> {
+        for channel in &mut self.0 {
+            unsafe { (channel.0.vtable.make_mut)(&mut channel.0) };
+        }
+        let (width, height) = (self.width(), self.height());
+        let image = if plane_stride(&self.0).is_some() {
+            self
+        } else {
+            Image::new_vec(self.into_vec_flat(), width, height)
+        };
+        let plane_stride =
+            plane_stride(&image.0).expect("Image::new_vec creates evenly spaced planes");
+        let first = &image.0[0].0;
+        export(
+            first.ptr,
+            [CHANNELS, height.get() as usize, width.get() as usize],
+            [plane_stride, first.stride, 1],
+            image,
+        )
+    }
+}
+
+/// Frees the tensor through its deleter, once the last channel referencing it is dropped
+struct TensorOwner(NonNull<DLManagedTensor>);
+
+// DLPack requires deleters to be callable from any thread
+unsafe impl Send for TensorOwner {}
+unsafe impl Sync for TensorOwner {}
+
+impl Drop for TensorOwner {
+    fn drop(&mut self) {
+        if let Some(deleter) = unsafe { self.0.as_ref() }.deleter {
+            unsafe { deleter(self.0.as_ptr()) };
+        }
+    }
+}
+
+impl<T: PixelTypePrimitive> ImageChannel<DynamicSize<T>> {
+    /// Imports a `height × width` or `height × width × pixel_elements` CPU tensor without copying.
+    /// Its deleter is called, once the last clone of the channel is dropped.
+    ///
+    /// # Errors
+    /// Fails if the device, data type, shape or strides are not supported. The tensor is returned and still owned by the caller.
+    ///
+    /// # Safety
+    /// `tensor` must point to a valid `DLManagedTensor`, which isn't used afterwards if this succeeds
+    pub unsafe fn try_from_dlpack(
+        tensor: NonNull<DLManagedTensor>,
+    ) -> Result<Self, IncompatibleTensorError> {
+        match unsafe { Self::import_layout(&tensor.as_ref().dl_tensor) } {
+            Ok((ptr, width, height, stride, pixel_elements)) => Ok(ImageChannel(unsafe {
+                UnsafeImageChannel::new_arc_owner(
+                    ptr,
+                    width,
+                    height,
+                    stride,
+                    pixel_elements,
+                    Arc::new(TensorOwner(tensor)),
+                )
+            })),
+            Err(reason) => Err(IncompatibleTensorError { tensor, reason }),
+        }
+    }
+
+    unsafe fn import_layout(
+        tensor: &DLTensor,
+    ) -> Result<(*const T, NonZeroU32, NonZeroU32, usize, NonZeroU8), IncompatibleTensorErrorReason>
+    {
+        if tensor.device.device_type != DLDeviceType::CPU {
+            return Err(IncompatibleTensorErrorReason::UnsupportedDevice {
+                device_type: tensor.device.device_type,
+            });
+        }
+        let expected = DLDataType::of::<T>();
+        if tensor.dtype != expected {
+            return Err(IncompatibleTensorErrorReason::IncompatibleDataType {
+                expected,
+                actual: tensor.dtype,
+            });
+        }
+        let ndim = usize::try_from(tensor.ndim).unwrap_or(0);
+        let shape = if tensor.shape.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(tensor.shape, ndim) }
+        };
+        let invalid_shape = || IncompatibleTensorErrorReason::InvalidShape {
+            shape: shape.to_vec(),
+        };
+        let (height, width, pixel_elements) = match *shape {
+            [height, width] => (height, width, 1),
+            [height, width, pixel_elements] => (height, width, pixel_elements),
+            _ => return Err(invalid_shape()),
+        };
+        let dimension = |x: i64| u32::try_from(x).ok().and_then(NonZeroU32::new);
+        let (Some(height), Some(width), Some(pixel_elements)) = (
+            dimension(height),
+            dimension(width),
+            u8::try_from(pixel_elements).ok().and_then(NonZeroU8::new),
+        ) else {
+            return Err(invalid_shape());
+        };
+
+        let row_len = width.get() as usize * usize::from(pixel_elements.get());
+        let stride = if tensor.strides.is_null() {
+            row_len
+        } else {
+            let strides = unsafe { std::slice::from_raw_parts(tensor.strides, shape.len()) };
+            let unsupported = || IncompatibleTensorErrorReason::UnsupportedStrides {
+                strides: strides.to_vec(),
+            };
+            let is_packed = (pixel_elements.get() == 1 || strides.get(2) == Some(&1))
+                && (width.get() == 1 || strides[1] == i64::from(pixel_elements.get()));
+            if !is_packed {
+                return Err(unsupported());
+            }
+            if height.get() == 1 {
+                row_len
+            } else {
+                usize::try_from(strides[0])
+                    .ok()
+                    .filter(|stride| *stride >= row_len)
+                    .ok_or_else(unsupported)?
+            }
+        };
+        let ptr = usize::try_from(tensor.byte_offset)
+            .ok()
+            .map(|offset| tensor.data.cast::<u8>().wrapping_add(offset).cast::<T>())
+            .filter(|ptr| ptr.is_aligned() && !ptr.is_null())
+            .ok_or(IncompatibleTensorErrorReason::Misaligned)?;
+        Ok((ptr, width, height, stride, pixel_elements))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        num::NonZeroU32,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+    use crate::{Image, ImageChannel};
+
+    fn tensor_shape(tensor: NonNull<DLManagedTensor>) -> (Vec<i64>, Vec<i64>) {
+        let tensor = unsafe { &tensor.as_ref().dl_tensor };
+        let ndim = usize::try_from(tensor.ndim).unwrap();
+        unsafe {
+            (
+                std::slice::from_raw_parts(tensor.shape, ndim).to_vec(),
+                std::slice::from_raw_parts(tensor.strides, ndim).to_vec(),
+            )
+        }
+    }
+
+    #[test]
+    fn channel_roundtrip_without_copy() {
+        let channel = ImageChannel::<[f32; 2]>::new_vec(
+            vec![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]],
+            NonZeroU32::new(3).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let ptr = channel.buffer().as_ptr().cast::<f32>();
+        let tensor = channel.into_dlpack();
+        assert_eq!(tensor_shape(tensor), (vec![1, 3, 2], vec![6, 2, 1]));
+
+        let imported =
+            unsafe { ImageChannel::<DynamicSize<f32>>::try_from_dlpack(tensor) }.unwrap();
+        assert_eq!(imported.buffer_flat().as_ptr(), ptr);
+        assert_eq!(
+            imported.try_cast::<[f32; 2]>().unwrap().buffer(),
+            &[[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]
+        );
+    }
+
+    #[test]
+    fn image_planes_share_one_tensor() {
+        let two = NonZeroU32::new(2).unwrap();
+        let image = Image::<u16, 3>::new_vec((0..12).collect(), two, two);
+        let ptr = image.buffers()[0].as_ptr();
+        let tensor = image.into_dlpack();
+        assert_eq!(tensor_shape(tensor), (vec![3, 2, 2], vec![4, 2, 1]));
+        assert_eq!(
+            unsafe { tensor.as_ref() }.dl_tensor.data.cast_const(),
+            ptr.cast()
+        );
+        unsafe { (tensor.as_ref().deleter.unwrap())(tensor.as_ptr()) };
+
+        let separate = Image::<u8, 2>::try_from([
+            ImageChannel::new_vec(vec![1], NonZeroU32::MIN, NonZeroU32::MIN),
+            ImageChannel::new_vec(vec![2], NonZeroU32::MIN, NonZeroU32::MIN),
+        ])
+        .unwrap();
+        let tensor = separate.into_dlpack();
+        let data = unsafe { tensor.as_ref() }.dl_tensor.data.cast::<u8>();
+        assert_eq!(unsafe { std::slice::from_raw_parts(data, 2) }, [1, 2]);
+        unsafe { (tensor.as_ref().deleter.unwrap())(tensor.as_ptr()) };
+    }
+
+    #[test]
+    fn import_calls_deleter_once() {
+        static DELETED: AtomicUsize = AtomicUsize::new(0);
+        unsafe extern "C" fn deleter(tensor: *mut DLManagedTensor) {
+            DELETED.fetch_add(1, Ordering::Relaxed);
+            drop(unsafe { Box::from_raw(tensor) });
+        }
+        let mut data = [0u8, 1, 2, 3, 4, 5, 6, 7];
+        let mut shape = [2i64, 2];
+        let mut strides = [4i64, 1];
+        let mut tensor = DLManagedTensor {
+            dl_tensor: DLTensor {
+                data: data.as_mut_ptr().cast(),
+                device: DLDevice {
+                    device_type: DLDeviceType::CPU,
+                    device_id: 0,
+                },
+                ndim: 2,
+                dtype: DLDataType::of::<u8>(),
+                shape: shape.as_mut_ptr(),
+                strides: strides.as_mut_ptr(),
+                byte_offset: 1,
+            },
+            manager_ctx: std::ptr::null_mut(),
+            deleter: Some(deleter),
+        };
+
+        tensor.dl_tensor.dtype = DLDataType::of::<i8>();
+        let error =
+            unsafe { ImageChannel::<DynamicSize<u8>>::try_from_dlpack(NonNull::from(&mut tensor)) }
+                .unwrap_err();
+        assert!(matches!(
+            error.reason,
+            IncompatibleTensorErrorReason::IncompatibleDataType { .. }
+        ));
+        tensor.dl_tensor.dtype = DLDataType::of::<u8>();
+
+        let tensor = NonNull::from(Box::leak(Box::new(tensor)));
+        let channel = unsafe { ImageChannel::<DynamicSize<u8>>::try_from_dlpack(tensor) }.unwrap();
+        let clone = channel.clone();
+        assert_eq!(channel.rows_flat().collect::<Vec<_>>(), [[1, 2], [5, 6]]);
+        drop(channel);
+        assert_eq!(DELETED.load(Ordering::Relaxed), 0);
+        drop(clone);
+        assert_eq!(DELETED.load(Ordering::Relaxed), 1);
+    }
+}
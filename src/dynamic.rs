@@ -4,14 +4,19 @@ use std::{
     num::{NonZeroU8, NonZeroU32, NonZeroUsize},
 };
 
-use crate::{Image, ImageChannel, ImageMut, ImageRef, PixelType, pixel::DynamicSize};
+use crate::{
+    ChannelSemantics, Image, ImageChannel, ImageChannels, ImageMut, ImageRef, Metadata, PixelType,
+    PixelTypePrimitive, pixel::DynamicSize,
+};
 
 /// Image with number of channels and their types and dimensions only known at runtime
 /// There are no guarantees that the types or dimensions of channels match. See `ImageChannels` for more information.
 /// The public interface is designed, so it can be extended to support images, which cannot be represented with Image (e.g. 1 Channel u8 and the other f32)
-#[derive(Debug, Clone, PartialEq)]
+/// Channels with different pixel types can be accessed as tuple (e.g. `ImageChannels<(ImageChannel<u8>, ImageChannel<f32>)>`)
+#[derive(Debug, Clone)]
 pub struct DynamicImage {
     channels: Vec<DynamicImageChannel>,
+    metadata: Metadata,
 }
 
 impl DynamicImage {
@@ -21,8 +26,33 @@ impl DynamicImage {
     ) -> Self {
         Self {
             channels: std::iter::once(first).chain(rest).collect(),
+            metadata: Metadata::new(),
         }
     }
+
+    /// Typed values, which are carried along with the pixels (see [`Metadata`])
+    #[must_use]
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Meaning of the pixel elements, which is stored in the [`Metadata`]
+    #[must_use]
+    pub fn semantics(&self) -> Option<ChannelSemantics> {
+        self.metadata.get().copied()
+    }
+
+    /// `None` removes the semantics
+    pub fn set_semantics(&mut self, semantics: Option<ChannelSemantics>) {
+        match semantics {
+            Some(semantics) => self.metadata.insert(semantics),
+            None => self.metadata.remove(),
+        };
+    }
     /// `DynamicImage` always has at least one channel, so this never panics
     #[must_use]
     pub fn first(&self) -> &DynamicImageChannel {
@@ -40,6 +70,18 @@ impl DynamicImage {
     pub fn len(&self) -> NonZeroUsize {
         NonZeroUsize::new(self.channels.len()).unwrap()
     }
+
+    #[cfg(feature = "ffi")]
+    pub(crate) fn push(&mut self, channel: DynamicImageChannel) {
+        self.channels.push(channel);
+    }
+}
+
+/// Metadata is ignored
+impl PartialEq for DynamicImage {
+    fn eq(&self, other: &Self) -> bool {
+        self.channels == other.channels
+    }
 }
 
 // Deref slice only, to make sure, noone can create a DynamicImage with a empty Vec
@@ -66,97 +108,179 @@ impl std::ops::DerefMut for DynamicImage {
     }
 }
 
+/// Variants depend on enabled features (e.g. `half`), so matches must have a wildcard arm
+#[non_exhaustive]
 #[derive(Debug, Clone, PartialEq)]
 pub enum DynamicImageChannel {
     U8(ImageChannel<DynamicSize<u8>>),
+    I8(ImageChannel<DynamicSize<i8>>),
     U16(ImageChannel<DynamicSize<u16>>),
+    I16(ImageChannel<DynamicSize<i16>>),
+    U32(ImageChannel<DynamicSize<u32>>),
+    I32(ImageChannel<DynamicSize<i32>>),
     F32(ImageChannel<DynamicSize<f32>>),
+    F64(ImageChannel<DynamicSize<f64>>),
+    #[cfg(feature = "half")]
+    F16(ImageChannel<DynamicSize<half::f16>>),
+    #[cfg(feature = "half")]
+    BF16(ImageChannel<DynamicSize<half::bf16>>),
+}
+
+/// Evaluates `$body` with `$channel` bound to the typed channel of every variant
+macro_rules! match_dynamic_channel {
+    ($value:expr, $channel:ident => $body:expr) => {
+        match $value {
+            DynamicImageChannel::U8($channel) => $body,
+            DynamicImageChannel::I8($channel) => $body,
+            DynamicImageChannel::U16($channel) => $body,
+            DynamicImageChannel::I16($channel) => $body,
+            DynamicImageChannel::U32($channel) => $body,
+            DynamicImageChannel::I32($channel) => $body,
+            DynamicImageChannel::F32($channel) => $body,
+            DynamicImageChannel::F64($channel) => $body,
+            #[cfg(feature = "half")]
+            DynamicImageChannel::F16($channel) => $body,
+            #[cfg(feature = "half")]
+            DynamicImageChannel::BF16($channel) => $body,
+        }
+    };
 }
+pub(crate) use match_dynamic_channel;
 
 impl DynamicImageChannel {
     #[must_use]
     pub fn pixel_elements(&self) -> NonZeroU8 {
-        match self {
-            DynamicImageChannel::U8(x) => x.pixel_elements(),
-            DynamicImageChannel::U16(x) => x.pixel_elements(),
-            DynamicImageChannel::F32(x) => x.pixel_elements(),
-        }
+        match_dynamic_channel!(self, x => x.pixel_elements())
     }
 
     #[must_use]
     pub fn dimensions(&self) -> (NonZeroU32, NonZeroU32) {
-        match self {
-            DynamicImageChannel::U8(x) => x.dimensions(),
-            DynamicImageChannel::U16(x) => x.dimensions(),
-            DynamicImageChannel::F32(x) => x.dimensions(),
-        }
+        match_dynamic_channel!(self, x => x.dimensions())
     }
 
     #[must_use]
     pub fn width(&self) -> NonZeroU32 {
-        match self {
-            DynamicImageChannel::U8(x) => x.width(),
-            DynamicImageChannel::U16(x) => x.width(),
-            DynamicImageChannel::F32(x) => x.width(),
-        }
+        match_dynamic_channel!(self, x => x.width())
     }
 
     #[must_use]
     pub fn height(&self) -> NonZeroU32 {
-        match self {
-            DynamicImageChannel::U8(x) => x.height(),
-            DynamicImageChannel::U16(x) => x.height(),
-            DynamicImageChannel::F32(x) => x.height(),
+        match_dynamic_channel!(self, x => x.height())
+    }
+
+    /// Distance between the start of two consecutive rows in primitives
+    #[must_use]
+    pub fn stride(&self) -> usize {
+        match_dynamic_channel!(self, x => x.stride())
+    }
+
+    #[must_use]
+    pub fn is_contiguous(&self) -> bool {
+        match_dynamic_channel!(self, x => x.is_contiguous())
+    }
+
+    /// Name of the primitive type (e.g. "u8") for diagnostics
+    #[must_use]
+    pub fn primitive_name(&self) -> &'static str {
+        fn name_of<T: PixelTypePrimitive>(_: &ImageChannel<DynamicSize<T>>) -> &'static str {
+            std::any::type_name::<T>()
         }
+        match_dynamic_channel!(self, x => name_of(x))
     }
 }
 
 impl<TPixel: PixelType + Send + Sync + Clone, const CHANNELS: usize> From<Image<TPixel, CHANNELS>>
     for DynamicImage
 {
-    fn from(value: Image<TPixel, CHANNELS>) -> Self {
+    fn from(ImageChannels(channels, metadata): Image<TPixel, CHANNELS>) -> Self {
         DynamicImage {
-            channels: <[ImageChannel<TPixel>; CHANNELS]>::from(value)
-                .into_iter()
-                .map(ImageChannel::into)
-                .collect(),
+            channels: channels.into_iter().map(ImageChannel::into).collect(),
+            metadata,
         }
     }
 }
 
 #[non_exhaustive]
 #[derive(Debug, thiserror::Error)]
-#[error("IncompatibleImageError: {image:?} {reason:?}")]
+#[error("IncompatibleImageError: {image:?} {reason}")]
 pub struct IncompatibleImageError<TInput> {
     pub image: TInput,
-    #[allow(dead_code)]
     pub(crate) reason: IncompatibleImageErrorReason,
 }
 
-#[derive(Debug)]
-#[allow(dead_code)]
-pub(crate) enum IncompatibleImageErrorReason {
-    // Todo: Rename me to something more descriptive
-    Comptime {
-        pixel_dimensions: NonZeroU8,
-        pixel_kind: &'static str,
-        buffer_dimensions: NonZeroUsize,
+impl<TInput> IncompatibleImageError<TInput> {
+    /// Why `image` couldn't be converted
+    #[must_use]
+    pub fn reason(&self) -> &IncompatibleImageErrorReason {
+        &self.reason
+    }
+
+    /// Returns the original input and the reason of the failure
+    #[must_use]
+    pub fn into_parts(self) -> (TInput, IncompatibleImageErrorReason) {
+        (self.image, self.reason)
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum IncompatibleImageErrorReason {
+    /// The primitive or the number of pixel elements of a channel doesn't match the requested pixel type
+    #[error(
+        "Channel {channel} has pixels [{actual_primitive}; {actual_elements}], expected [{expected_primitive}; {expected_elements}]"
+    )]
+    IncompatiblePixelType {
+        channel: usize,
+        expected_primitive: &'static str,
+        expected_elements: NonZeroU8,
+        actual_primitive: &'static str,
+        actual_elements: NonZeroU8,
     },
+    /// Channels have different dimensions
+    #[error("Channels have different dimensions: {a:?} and {b:?}")]
     MixedImageSizes {
         a: (NonZeroU32, NonZeroU32),
         b: (NonZeroU32, NonZeroU32),
     },
+    /// The image has less channels than requested
+    #[error("Requires {expected} channels, but only {actual} are available")]
     RequiresMoreChannels {
         expected: NonZeroU8,
         actual: NonZeroU8,
     },
+    /// A channel doesn't have the dimensions required by its image format (e.g. subsampled chroma)
+    #[error("Channel {channel} has dimensions {actual:?}, expected {expected:?}")]
+    UnexpectedDimensions {
+        channel: usize,
+        expected: (NonZeroU32, NonZeroU32),
+        actual: (NonZeroU32, NonZeroU32),
+    },
+    /// The image format stores pairs of pixels (e.g. YUYV)
+    #[error("Channel {channel} requires an even width, got {width}")]
+    RequiresEvenWidth { channel: usize, width: NonZeroU32 },
+}
+
+impl IncompatibleImageErrorReason {
+    pub(crate) fn incompatible_pixel_type<T: PixelType>(
+        channel: usize,
+        actual: &DynamicImageChannel,
+    ) -> Self {
+        Self::IncompatiblePixelType {
+            channel,
+            expected_primitive: std::any::type_name::<T::Primitive>(),
+            expected_elements: T::ELEMENTS,
+            actual_primitive: actual.primitive_name(),
+            actual_elements: actual.pixel_elements(),
+        }
+    }
 }
 
 impl<T: PixelType, const CHANNELS: usize> TryFrom<DynamicImage> for Image<T, CHANNELS> {
     type Error = IncompatibleImageError<DynamicImage>;
 
     fn try_from(value: DynamicImage) -> Result<Self, Self::Error> {
-        let mut value = value.channels.into_iter();
+        let DynamicImage { channels, metadata } = value;
+        let mut value = channels.into_iter();
         let mut incompatible_image = Ok(());
         let mut prev_image_size = None;
 
@@ -179,14 +303,12 @@ impl<T: PixelType, const CHANNELS: usize> TryFrom<DynamicImage> for Image<T, CHA
                                     }
                                 }
                             }
-                            Err(dynamic) => (
-                                Some(dynamic),
-                                IncompatibleImageErrorReason::Comptime {
-                                    pixel_dimensions: T::ELEMENTS,
-                                    pixel_kind: std::any::type_name::<T>(),
-                                    buffer_dimensions: const { NonZeroUsize::new(CHANNELS).unwrap() },
-                                },
-                            ),
+                            Err(dynamic) => {
+                                let reason = IncompatibleImageErrorReason::incompatible_pixel_type::<
+                                    T,
+                                >(i, &dynamic);
+                                (Some(dynamic), reason)
+                            }
                         },
                         None => (
                             None,
@@ -201,10 +323,10 @@ impl<T: PixelType, const CHANNELS: usize> TryFrom<DynamicImage> for Image<T, CHA
             MaybeUninit::uninit()
         });
         match incompatible_image {
-            Ok(()) => Ok(all
-                .map(|x| unsafe { x.assume_init() })
-                .try_into()
-                .expect("Preconditions are checked already")),
+            Ok(()) => Ok(ImageChannels(
+                all.map(|x| unsafe { x.assume_init() }),
+                metadata,
+            )),
             Err((initialized_indices, (error_image, reason))) => Err(IncompatibleImageError {
                 image: DynamicImage {
                     channels: all
@@ -214,6 +336,7 @@ impl<T: PixelType, const CHANNELS: usize> TryFrom<DynamicImage> for Image<T, CHA
                         .chain(error_image)
                         .chain(value)
                         .collect(),
+                    metadata,
                 },
                 reason,
             }),
@@ -236,11 +359,9 @@ impl<'a, T: PixelType, const CHANNELS: usize> TryFrom<&'a DynamicImage>
                         Ok(typed) => {
                             return MaybeUninit::new(typed);
                         }
-                        Err(_e) => Err(IncompatibleImageErrorReason::Comptime {
-                            pixel_dimensions: T::ELEMENTS,
-                            pixel_kind: std::any::type_name::<T>(),
-                            buffer_dimensions: const { NonZeroUsize::new(CHANNELS).unwrap() },
-                        }),
+                        Err(()) => Err(IncompatibleImageErrorReason::incompatible_pixel_type::<T>(
+                            i, next,
+                        )),
                     }
                 } else {
                     Err(IncompatibleImageErrorReason::RequiresMoreChannels {
@@ -248,14 +369,15 @@ impl<'a, T: PixelType, const CHANNELS: usize> TryFrom<&'a DynamicImage>
                         actual: crate::unwrap_usize_to_nonzero_u8(i),
                     })
                 }
-            };
+            }
             MaybeUninit::uninit()
         });
         result
-            .and_then(|_| {
+            .and_then(|()| {
                 <ImageRef<'a, T, CHANNELS>>::try_from(channels.map(|x| unsafe { x.assume_init() }))
                     .map_err(|x: IncompatibleImageError<[&ImageChannel<T>; CHANNELS]>| x.reason)
             })
+            .map(|image| ImageChannels(image.0, value.metadata.clone()))
             .map_err(|reason| IncompatibleImageError {
                 image: value,
                 reason,
@@ -274,15 +396,14 @@ impl<'a, T: PixelType, const CHANNELS: usize> TryFrom<&'a mut DynamicImage>
         let channels = std::array::from_fn(|i| {
             if result.is_ok() {
                 result = if let Some(next) = iter.next() {
+                    // Evaluated upfront, as `next` is moved into the typed channel
+                    let reason =
+                        IncompatibleImageErrorReason::incompatible_pixel_type::<T>(i, next);
                     match <&mut ImageChannel<T>>::try_from(next) {
                         Ok(typed) => {
                             return MaybeUninit::new(typed);
                         }
-                        Err(_e) => Err(IncompatibleImageErrorReason::Comptime {
-                            pixel_dimensions: T::ELEMENTS,
-                            pixel_kind: std::any::type_name::<T>(),
-                            buffer_dimensions: const { NonZeroUsize::new(CHANNELS).unwrap() },
-                        }),
+                        Err(()) => Err(reason),
                     }
                 } else {
                     Err(IncompatibleImageErrorReason::RequiresMoreChannels {
@@ -290,14 +411,15 @@ impl<'a, T: PixelType, const CHANNELS: usize> TryFrom<&'a mut DynamicImage>
                         actual: crate::unwrap_usize_to_nonzero_u8(i),
                     })
                 }
-            };
+            }
             MaybeUninit::uninit()
         });
         result
-            .and_then(|_| {
+            .and_then(|()| {
                 <ImageMut<'a, T, CHANNELS>>::try_from(channels.map(|x| unsafe { x.assume_init() }))
                     .map_err(|x: IncompatibleImageError<[&mut ImageChannel<T>; CHANNELS]>| x.reason)
             })
+            .map(|image| ImageChannels(image.0, value.metadata.clone()))
             .map_err(|reason| IncompatibleImageError {
                 image: value,
                 reason,
@@ -410,4 +532,36 @@ mod tests {
         let incompatible = Image::<u16, 1>::try_from(dynamic).unwrap_err();
         assert_eq!(incompatible.image, DynamicImage::from(luma));
     }
+
+    #[test]
+    fn create_from_extended_primitives() {
+        fn roundtrip<T: PixelType + Send + Sync>(value: T) {
+            let image = Image::<T, 1>::new_vec(vec![value], NonZeroU32::MIN, NonZeroU32::MIN);
+            let dynamic = DynamicImage::from(image.clone());
+            assert_eq!(
+                dynamic.first().primitive_name(),
+                std::any::type_name::<T::Primitive>()
+            );
+            let back: Image<T, 1> = dynamic.try_into().unwrap();
+            assert_eq!(back, image);
+        }
+        roundtrip(-1i8);
+        roundtrip([-1i16, 2]);
+        roundtrip(u32::MAX);
+        roundtrip([i32::MIN; 3]);
+        roundtrip(0.5f64);
+        #[cfg(feature = "half")]
+        {
+            roundtrip([half::f16::ONE; 2]);
+            roundtrip(half::bf16::ONE);
+        }
+    }
+
+    #[test]
+    fn signed_and_unsigned_are_incompatible() {
+        let image = Image::<u32, 1>::new_vec(vec![1], NonZeroU32::MIN, NonZeroU32::MIN);
+        let dynamic = DynamicImage::from(image);
+        assert!(ImageRef::<i32, 1>::try_from(&dynamic).is_err());
+        Image::<i32, 1>::try_from(dynamic).unwrap_err();
+    }
 }
@@ -1,8 +1,17 @@
 #[cfg(feature = "image_0_25")]
 mod image_0_25;
 
+#[cfg(feature = "nalgebra")]
+mod nalgebra_0_34;
+#[cfg(feature = "ndarray")]
+mod ndarray_0_17;
+
 #[cfg(feature = "image_0_25")]
 pub use image_0_25::*;
+#[cfg(feature = "nalgebra")]
+pub use nalgebra_0_34::*;
+#[cfg(feature = "ndarray")]
+pub use ndarray_0_17::*;
 
 #[derive(Debug, thiserror::Error)]
 #[error("The image has a wrong length. Expected {expected}, got {actual}")]
@@ -1,8 +1,14 @@
 use std::num::{NonZeroU8, NonZeroU32};
 
-use image_0_25::{DynamicImage, GenericImageView, ImageBuffer, Luma, LumaA, Rgb, Rgba};
+use image_0_25::{
+    DynamicImage, GenericImageView, ImageBuffer, Luma, LumaA, Rgb, Rgba,
+    metadata::CicpTransferCharacteristics,
+};
 
-use crate::{DynamicImageChannel, Image, IncompatibleBufferSize};
+use crate::{
+    ChannelLayout, ChannelSemantics, DynamicImageChannel, Image, IncompatibleBufferSize,
+    TransferFunction,
+};
 
 #[derive(thiserror::Error, Debug)]
 #[error("Cannot convert {image:?} into DynamicImage: {reason}")]
@@ -48,6 +54,17 @@ pub enum IntoDynamicRefImage0_25Error {
         channels: NonZeroU8,
         pixel_elements: NonZeroU8,
     },
+    #[error("The image rows are not contiguous in memory (stride={stride})")]
+    NonContiguous { stride: usize },
+    #[error("image 0.25 has no representation for {primitive} channels")]
+    UnsupportedPrimitive { primitive: &'static str },
+    #[error("image 0.25 has no representation for {layout:?} pixels")]
+    UnsupportedLayout { layout: ChannelLayout },
+    #[error("{layout:?} requires {} pixel elements, got {pixel_elements}", layout.elements())]
+    IncompatibleSemantics {
+        layout: ChannelLayout,
+        pixel_elements: NonZeroU8,
+    },
 }
 
 impl DynamicRefImage0_25<'_> {
@@ -71,8 +88,28 @@ impl DynamicRefImage0_25<'_> {
             DynamicRefImage0_25::ImageRgba32F(x) => x.write_to(&mut buffer, format),
         }
     }
+
+    fn set_transfer_function(&mut self, transfer: TransferFunction) {
+        let transfer = match transfer {
+            TransferFunction::Linear => CicpTransferCharacteristics::Linear,
+            TransferFunction::Srgb => CicpTransferCharacteristics::SRgb,
+        };
+        match self {
+            DynamicRefImage0_25::ImageLuma8(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageLuma16(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageLumaA8(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageLumaA16(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageRgb8(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageRgb16(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageRgb32F(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageRgba8(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageRgba16(x) => x.set_transfer_function(transfer),
+            DynamicRefImage0_25::ImageRgba32F(x) => x.set_transfer_function(transfer),
+        }
+    }
 }
 
+/// Uses [`crate::DynamicImage::semantics`] if available. Otherwise, pixels with 3 or 4 elements are assumed to be RGB(A)
 impl<'a> TryFrom<&'a crate::DynamicImage> for DynamicRefImage0_25<'a> {
     type Error = IntoDynamicRefImage0_25Error;
 
@@ -87,86 +124,110 @@ impl<'a> TryFrom<&'a crate::DynamicImage> for DynamicRefImage0_25<'a> {
             });
         }
 
+        if !channel.is_contiguous() {
+            return Err(IntoDynamicRefImage0_25Error::NonContiguous {
+                stride: channel.stride(),
+            });
+        }
+
+        let semantics = value.semantics();
+        if let Some(semantics) = semantics {
+            check_ref_semantics(semantics, pixel_elements)?;
+        }
+
         let width = channel.width().get();
         let height = channel.height().get();
 
-        match (channel, pixel_elements.get()) {
-            (DynamicImageChannel::U8(x), 1) => {
-                Ok(DynamicRefImage0_25::ImageLuma8(ref_image::<Luma<u8>, u8>(
-                    width,
-                    height,
-                    x.buffer_flat(),
-                )))
-            }
-            (DynamicImageChannel::U8(x), 2) => {
-                Ok(DynamicRefImage0_25::ImageLumaA8(
+        let mut image =
+            match (channel, pixel_elements.get()) {
+                (DynamicImageChannel::U8(x), 1) => Ok(DynamicRefImage0_25::ImageLuma8(
+                    ref_image::<Luma<u8>, u8>(width, height, x.buffer_flat()),
+                )),
+                (DynamicImageChannel::U8(x), 2) => Ok(DynamicRefImage0_25::ImageLumaA8(
                     ref_image::<LumaA<u8>, u8>(width, height, x.buffer_flat()),
-                ))
-            }
-            (DynamicImageChannel::U8(x), 3) => {
-                Ok(DynamicRefImage0_25::ImageRgb8(ref_image::<Rgb<u8>, u8>(
-                    width,
-                    height,
-                    x.buffer_flat(),
-                )))
-            }
-            (DynamicImageChannel::U8(x), 4) => {
-                Ok(DynamicRefImage0_25::ImageRgba8(ref_image::<Rgba<u8>, u8>(
-                    width,
-                    height,
-                    x.buffer_flat(),
-                )))
-            }
-            (DynamicImageChannel::U16(x), 1) => {
-                Ok(DynamicRefImage0_25::ImageLuma16(
+                )),
+                (DynamicImageChannel::U8(x), 3) => {
+                    Ok(DynamicRefImage0_25::ImageRgb8(ref_image::<Rgb<u8>, u8>(
+                        width,
+                        height,
+                        x.buffer_flat(),
+                    )))
+                }
+                (DynamicImageChannel::U8(x), 4) => Ok(DynamicRefImage0_25::ImageRgba8(
+                    ref_image::<Rgba<u8>, u8>(width, height, x.buffer_flat()),
+                )),
+                (DynamicImageChannel::U16(x), 1) => Ok(DynamicRefImage0_25::ImageLuma16(
                     ref_image::<Luma<u16>, u16>(width, height, x.buffer_flat()),
-                ))
-            }
-            (DynamicImageChannel::U16(x), 2) => Ok(DynamicRefImage0_25::ImageLumaA16(ref_image::<
-                LumaA<u16>,
-                u16,
-            >(
-                width,
-                height,
-                x.buffer_flat(),
-            ))),
-            (DynamicImageChannel::U16(x), 3) => {
-                Ok(DynamicRefImage0_25::ImageRgb16(ref_image::<Rgb<u16>, u16>(
-                    width,
-                    height,
-                    x.buffer_flat(),
-                )))
-            }
-            (DynamicImageChannel::U16(x), 4) => {
-                Ok(DynamicRefImage0_25::ImageRgba16(
+                )),
+                (DynamicImageChannel::U16(x), 2) => {
+                    Ok(DynamicRefImage0_25::ImageLumaA16(ref_image::<
+                        LumaA<u16>,
+                        u16,
+                    >(
+                        width,
+                        height,
+                        x.buffer_flat(),
+                    )))
+                }
+                (DynamicImageChannel::U16(x), 3) => Ok(DynamicRefImage0_25::ImageRgb16(
+                    ref_image::<Rgb<u16>, u16>(width, height, x.buffer_flat()),
+                )),
+                (DynamicImageChannel::U16(x), 4) => Ok(DynamicRefImage0_25::ImageRgba16(
                     ref_image::<Rgba<u16>, u16>(width, height, x.buffer_flat()),
-                ))
-            }
-            (DynamicImageChannel::F32(x), 3) => {
-                Ok(DynamicRefImage0_25::ImageRgb32F(
+                )),
+                (DynamicImageChannel::F32(x), 3) => Ok(DynamicRefImage0_25::ImageRgb32F(
                     ref_image::<Rgb<f32>, f32>(width, height, x.buffer_flat()),
-                ))
-            }
-            (DynamicImageChannel::F32(x), 4) => {
-                Ok(DynamicRefImage0_25::ImageRgba32F(
+                )),
+                (DynamicImageChannel::F32(x), 4) => Ok(DynamicRefImage0_25::ImageRgba32F(
                     ref_image::<Rgba<f32>, f32>(width, height, x.buffer_flat()),
-                ))
-            }
-            (_, actual) => Err(IntoDynamicRefImage0_25Error::IncompatibleLayout {
-                channels: crate::unwrap_usize_to_nonzero_u8(value.len().get()),
-                pixel_elements: NonZeroU8::new(actual).unwrap(),
-            }),
+                )),
+                (
+                    DynamicImageChannel::I8(_)
+                    | DynamicImageChannel::I16(_)
+                    | DynamicImageChannel::U32(_)
+                    | DynamicImageChannel::I32(_)
+                    | DynamicImageChannel::F64(_),
+                    _,
+                ) => Err(IntoDynamicRefImage0_25Error::UnsupportedPrimitive {
+                    primitive: channel.primitive_name(),
+                }),
+                #[cfg(feature = "half")]
+                (DynamicImageChannel::F16(_) | DynamicImageChannel::BF16(_), _) => {
+                    Err(IntoDynamicRefImage0_25Error::UnsupportedPrimitive {
+                        primitive: channel.primitive_name(),
+                    })
+                }
+                (_, actual) => Err(IntoDynamicRefImage0_25Error::IncompatibleLayout {
+                    channels: crate::unwrap_usize_to_nonzero_u8(value.len().get()),
+                    pixel_elements: NonZeroU8::new(actual).unwrap(),
+                }),
+            }?;
+        if let Some(transfer) = semantics.and_then(|x| x.transfer) {
+            image.set_transfer_function(transfer);
         }
+        Ok(image)
     }
 }
 
 macro_rules! impl_from_image_ref_dynamic {
     ($src:ty, $pixel:ty, $variant:ident) => {
-        impl<'a> From<&'a Image<$src, 1>> for DynamicRefImage0_25<'a> {
-            fn from(value: &'a Image<$src, 1>) -> Self {
+        /// Fails with [`IntoDynamicRefImage0_25Error::NonContiguous`] for padded channels (e.g. [`crate::ImageChannel::new_aligned`])
+        impl<'a> TryFrom<&'a Image<$src, 1>> for DynamicRefImage0_25<'a> {
+            type Error = IntoDynamicRefImage0_25Error;
+
+            fn try_from(value: &'a Image<$src, 1>) -> Result<Self, Self::Error> {
                 let (width, height) = value.dimensions();
-                let buffer = value.buffer_flat();
-                DynamicRefImage0_25::$variant(ref_image(width.get(), height.get(), buffer))
+                let [channel] = &value.0;
+                let buffer = channel.try_buffer_flat().ok_or(
+                    IntoDynamicRefImage0_25Error::NonContiguous {
+                        stride: channel.stride(),
+                    },
+                )?;
+                Ok(DynamicRefImage0_25::$variant(ref_image(
+                    width.get(),
+                    height.get(),
+                    buffer,
+                )))
             }
         }
     };
@@ -208,6 +269,8 @@ impl_from_image_dynamic!([u16; 4], Rgba<u16>, ImageRgba16);
 impl_from_image_dynamic!([f32; 4], Rgba<f32>, ImageRgba32F);
 
 /// Only fails, if `image::Image.width()` or `image::Image.height()` is 0
+///
+/// Sets [`crate::DynamicImage::semantics`] according to the pixel type and color space
 impl TryFrom<DynamicImage> for crate::DynamicImage {
     type Error = IntoDynamicImage0_25Error;
 
@@ -233,7 +296,8 @@ impl TryFrom<DynamicImage> for crate::DynamicImage {
                 });
             }
         };
-        Ok(match value {
+        let semantics = semantics_of(&value);
+        let mut image: Self = match value {
             DynamicImage::ImageLuma8(x) => {
                 Image::<u8, 1>::new_vec(extract_vec(x, width_times_height)?, width, height).into()
             }
@@ -294,8 +358,49 @@ impl TryFrom<DynamicImage> for crate::DynamicImage {
                     reason: IntoDynamicImage0_25ErrorReason::NonExhaustive,
                 });
             }
-        })
+        };
+        image.set_semantics(semantics);
+        Ok(image)
+    }
+}
+
+fn check_ref_semantics(
+    ChannelSemantics { layout, .. }: ChannelSemantics,
+    pixel_elements: NonZeroU8,
+) -> Result<(), IntoDynamicRefImage0_25Error> {
+    if !matches!(
+        layout,
+        ChannelLayout::Gray | ChannelLayout::GrayAlpha | ChannelLayout::Rgb | ChannelLayout::Rgba
+    ) {
+        return Err(IntoDynamicRefImage0_25Error::UnsupportedLayout { layout });
+    }
+    if layout.elements() != pixel_elements {
+        return Err(IntoDynamicRefImage0_25Error::IncompatibleSemantics {
+            layout,
+            pixel_elements,
+        });
     }
+    Ok(())
+}
+
+fn semantics_of(image: &DynamicImage) -> Option<ChannelSemantics> {
+    let layout = match image {
+        DynamicImage::ImageLuma8(_) | DynamicImage::ImageLuma16(_) => ChannelLayout::Gray,
+        DynamicImage::ImageLumaA8(_) | DynamicImage::ImageLumaA16(_) => ChannelLayout::GrayAlpha,
+        DynamicImage::ImageRgb8(_) | DynamicImage::ImageRgb16(_) | DynamicImage::ImageRgb32F(_) => {
+            ChannelLayout::Rgb
+        }
+        DynamicImage::ImageRgba8(_)
+        | DynamicImage::ImageRgba16(_)
+        | DynamicImage::ImageRgba32F(_) => ChannelLayout::Rgba,
+        _ => return None,
+    };
+    let semantics = ChannelSemantics::new(layout);
+    Some(match image.color_space().transfer {
+        CicpTransferCharacteristics::Linear => semantics.with_transfer(TransferFunction::Linear),
+        CicpTransferCharacteristics::SRgb => semantics.with_transfer(TransferFunction::Srgb),
+        _ => semantics,
+    })
 }
 
 fn extract_vec<TPixel: image_0_25::Pixel>(
@@ -343,7 +448,9 @@ mod tests {
     #[allow(unused_imports)] // Bug, probably because of crate renaming
     use image_0_25::DynamicImage;
 
-    use crate::{DynamicRefImage0_25, Image};
+    use image_0_25::metadata::CicpTransferCharacteristics;
+
+    use crate::{ChannelLayout, ChannelSemantics, DynamicRefImage0_25, Image, TransferFunction};
 
     #[test]
     fn test_try_from_dynamic_luma_image() {
@@ -395,11 +502,14 @@ mod tests {
         let expected = expected.into_inner();
         let image = Image::<u8, 1>::new_vec(vec![1], NonZeroU32::MIN, NonZeroU32::MIN);
 
-        assert_eq!(expected, test_encode(DynamicRefImage0_25::from(&image)));
+        assert_eq!(
+            expected,
+            test_encode(&DynamicRefImage0_25::try_from(&image).unwrap())
+        );
         let dynamic = crate::DynamicImage::from(image);
         assert_eq!(
             expected,
-            test_encode(DynamicRefImage0_25::try_from(&dynamic).unwrap())
+            test_encode(&DynamicRefImage0_25::try_from(&dynamic).unwrap())
         );
     }
 
@@ -415,15 +525,88 @@ mod tests {
         let image =
             Image::<[u16; 3], 1>::new_vec(vec![[0, 1, 2]], NonZeroU32::MIN, NonZeroU32::MIN);
 
-        assert_eq!(expected, test_encode(DynamicRefImage0_25::from(&image)));
+        assert_eq!(
+            expected,
+            test_encode(&DynamicRefImage0_25::try_from(&image).unwrap())
+        );
         let dynamic = crate::DynamicImage::from(image);
         assert_eq!(
             expected,
-            test_encode(DynamicRefImage0_25::try_from(&dynamic).unwrap())
+            test_encode(&DynamicRefImage0_25::try_from(&dynamic).unwrap())
+        );
+    }
+
+    #[test]
+    fn i16_has_no_dynamic_ref_image() {
+        let image = Image::<i16, 1>::new_vec(vec![-1], NonZeroU32::MIN, NonZeroU32::MIN);
+        let dynamic = crate::DynamicImage::from(image);
+        assert!(matches!(
+            DynamicRefImage0_25::try_from(&dynamic),
+            Err(crate::IntoDynamicRefImage0_25Error::UnsupportedPrimitive { primitive: "i16" })
+        ));
+    }
+
+    #[test]
+    fn padded_image_has_no_ref_image() {
+        let channel = crate::ImageChannel::<[u8; 3]>::new_aligned(
+            &[[1, 2, 3]; 2],
+            NonZeroU32::MIN,
+            NonZeroU32::new(2).unwrap(),
+            64,
+        );
+        let image = Image::<[u8; 3], 1>::try_from([channel]).unwrap();
+        assert!(matches!(
+            DynamicRefImage0_25::try_from(&image),
+            Err(crate::IntoDynamicRefImage0_25Error::NonContiguous { stride: 64 })
+        ));
+    }
+
+    #[test]
+    fn semantics_roundtrip() {
+        let mut image = DynamicImage::new_rgba8(1, 1);
+        image.set_transfer_function(CicpTransferCharacteristics::Linear);
+        let dynamic = crate::DynamicImage::try_from(image).unwrap();
+        let semantics = dynamic.semantics().unwrap();
+        assert_eq!(semantics.layout, ChannelLayout::Rgba);
+        assert_eq!(semantics.transfer, Some(TransferFunction::Linear));
+
+        let DynamicRefImage0_25::ImageRgba8(image) =
+            DynamicRefImage0_25::try_from(&dynamic).unwrap()
+        else {
+            panic!("Expected Rgba8");
+        };
+        assert_eq!(
+            image.color_space().transfer,
+            CicpTransferCharacteristics::Linear
         );
     }
 
-    fn test_encode(image: DynamicRefImage0_25<'_>) -> Vec<u8> {
+    #[test]
+    fn semantics_select_or_reject_ref_image() {
+        let image = Image::<[u8; 3], 1>::new_vec(vec![[0, 1, 2]], NonZeroU32::MIN, NonZeroU32::MIN);
+        let mut dynamic = crate::DynamicImage::from(image);
+        dynamic.set_semantics(Some(ChannelSemantics::new(ChannelLayout::Bgr)));
+        assert!(matches!(
+            DynamicRefImage0_25::try_from(&dynamic),
+            Err(crate::IntoDynamicRefImage0_25Error::UnsupportedLayout {
+                layout: ChannelLayout::Bgr
+            })
+        ));
+        dynamic.set_semantics(Some(ChannelSemantics::new(ChannelLayout::Rgba)));
+        assert!(matches!(
+            DynamicRefImage0_25::try_from(&dynamic),
+            Err(crate::IntoDynamicRefImage0_25Error::IncompatibleSemantics { .. })
+        ));
+        dynamic.set_semantics(Some(
+            ChannelSemantics::new(ChannelLayout::Rgb).with_transfer(TransferFunction::Srgb),
+        ));
+        assert!(matches!(
+            DynamicRefImage0_25::try_from(&dynamic),
+            Ok(DynamicRefImage0_25::ImageRgb8(_))
+        ));
+    }
+
+    fn test_encode(image: &DynamicRefImage0_25<'_>) -> Vec<u8> {
         let mut expected = Cursor::new(Vec::new());
         let format = image_0_25::ImageFormat::Png;
         image.write_to(&mut expected, format).unwrap();
@@ -0,0 +1,166 @@
+use std::num::{NonZeroU8, NonZeroU32};
+
+use nalgebra::{DMatrix, DMatrixView, DMatrixViewMut, Dyn, Scalar};
+
+use crate::{ImageChannel, PixelTypePrimitive, channel::UnsafeImageChannel};
+
+/// How matrix indices map onto pixels
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixOrder {
+    /// `matrix[(y, x)]`, so the matrix has `height` rows.
+    /// Owned matrices are transposed when converted, as nalgebra stores columns consecutively.
+    RowMajor,
+    /// `matrix[(x, y)]`, so the matrix has `width` rows.
+    /// Matches the memory layout of nalgebra, so owned matrices are converted without copying.
+    ColumnMajor,
+}
+
+#[derive(Debug, thiserror::Error)]
+#[error("Matrices require 1..=u32::MAX rows and columns, got {}x{}", matrix.nrows(), matrix.ncols())]
+#[non_exhaustive]
+pub struct IncompatibleMatrixError<T: Scalar> {
+    pub matrix: DMatrix<T>,
+}
+
+impl<T: PixelTypePrimitive + Scalar> ImageChannel<T> {
+    /// Borrows the channel as matrix without copying. Row padding is skipped via strides.
+    #[must_use]
+    pub fn as_matrix_view(&self, order: MatrixOrder) -> DMatrixView<'_, T, Dyn, Dyn> {
+        let (nrows, ncols, rstride, cstride) = matrix_layout(&self.0, order);
+        let data = unsafe { std::slice::from_raw_parts(self.0.ptr, strided_len(&self.0)) };
+        DMatrixView::from_slice_with_strides_generic(data, nrows, ncols, rstride, cstride)
+    }
+
+    /// Like [`Self::as_matrix_view`], but copies shared buffers first (see [`ImageChannel::make_mut`])
+    pub fn as_matrix_view_mut(&mut self, order: MatrixOrder) -> DMatrixViewMut<'_, T, Dyn, Dyn> {
+        let (nrows, ncols, rstride, cstride) = matrix_layout(&self.0, order);
+        let data = unsafe {
+            (self.0.vtable.make_mut)(&mut self.0);
+            std::slice::from_raw_parts_mut(self.0.ptr.cast_mut(), strided_len(&self.0))
+        };
+        DMatrixViewMut::from_slice_with_strides_generic(data, nrows, ncols, rstride, cstride)
+    }
+
+    #[must_use]
+    pub fn to_matrix(&self, order: MatrixOrder) -> DMatrix<T> {
+        self.as_matrix_view(order).into_owned()
+    }
+
+    /// Reuses the allocation of `matrix` for [`MatrixOrder::ColumnMajor`]
+    ///
+    /// # Errors
+    /// Returns the matrix, if it has no rows/columns or more than `u32::MAX`
+    pub fn try_from_matrix(
+        matrix: DMatrix<T>,
+        order: MatrixOrder,
+    ) -> Result<Self, IncompatibleMatrixError<T>> {
+        let (width, height) = match order {
+            MatrixOrder::RowMajor => (matrix.ncols(), matrix.nrows()),
+            MatrixOrder::ColumnMajor => (matrix.nrows(), matrix.ncols()),
+        };
+        let dimensions = u32::try_from(width)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .zip(u32::try_from(height).ok().and_then(NonZeroU32::new));
+        let Some((width, height)) = dimensions else {
+            return Err(IncompatibleMatrixError { matrix });
+        };
+        let column_major = match order {
+            MatrixOrder::RowMajor => matrix.transpose(),
+            MatrixOrder::ColumnMajor => matrix,
+        };
+        Ok(ImageChannel(UnsafeImageChannel::new_vec(
+            column_major.data.into(),
+            width,
+            height,
+            NonZeroU8::MIN,
+        )))
+    }
+}
+
+fn matrix_layout<T>(channel: &UnsafeImageChannel<T>, order: MatrixOrder) -> (Dyn, Dyn, Dyn, Dyn) {
+    let width = Dyn(channel.width.get() as usize);
+    let height = Dyn(channel.height.get() as usize);
+    let stride = Dyn(channel.stride);
+    match order {
+        MatrixOrder::RowMajor => (height, width, stride, Dyn(1)),
+        MatrixOrder::ColumnMajor => (width, height, Dyn(1), stride),
+    }
+}
+
+/// Number of primitives from the first to the last pixel, including the padding in between
+fn strided_len<T>(channel: &UnsafeImageChannel<T>) -> usize {
+    (channel.height.get() as usize - 1) * channel.stride + channel.row_len_flat()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use nalgebra::DMatrix;
+
+    use super::MatrixOrder;
+    use crate::ImageChannel;
+
+    fn channel() -> ImageChannel<u16> {
+        ImageChannel::new_vec(
+            vec![1, 2, 3, 4, 5, 6],
+            NonZeroU32::new(3).unwrap(),
+            NonZeroU32::new(2).unwrap(),
+        )
+    }
+
+    #[test]
+    fn views_in_both_orders() {
+        let channel = channel();
+        let view = channel.as_matrix_view(MatrixOrder::RowMajor);
+        assert_eq!(view.shape(), (2, 3));
+        assert_eq!(view[(1, 0)], 4);
+        let view = channel.as_matrix_view(MatrixOrder::ColumnMajor);
+        assert_eq!(view.shape(), (3, 2));
+        assert_eq!(view[(0, 1)], 4);
+        assert_eq!(view.as_ptr(), channel.buffer().as_ptr());
+    }
+
+    #[test]
+    fn strided_view() {
+        let channel = channel();
+        let view = channel
+            .view(
+                1,
+                0,
+                NonZeroU32::new(2).unwrap(),
+                NonZeroU32::new(2).unwrap(),
+            )
+            .unwrap();
+        assert_eq!(
+            view.to_matrix(MatrixOrder::RowMajor),
+            DMatrix::from_row_slice(2, 2, &[2, 3, 5, 6])
+        );
+    }
+
+    #[test]
+    fn mutable_view_copies_shared_buffer() {
+        let mut channel = channel();
+        let clone = channel.clone();
+        channel.as_matrix_view_mut(MatrixOrder::RowMajor)[(0, 2)] = 42;
+        assert_eq!(channel.buffer(), &[1, 2, 42, 4, 5, 6]);
+        assert_eq!(clone.buffer(), &[1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn from_matrix() {
+        let matrix = DMatrix::from_row_slice(2, 3, &[1u16, 2, 3, 4, 5, 6]);
+        let channel = ImageChannel::try_from_matrix(matrix.clone(), MatrixOrder::RowMajor).unwrap();
+        assert_eq!(channel, self::channel());
+
+        let ptr = matrix.as_ptr();
+        let channel = ImageChannel::try_from_matrix(matrix, MatrixOrder::ColumnMajor).unwrap();
+        assert_eq!(channel.dimensions().0.get(), 2);
+        assert_eq!(channel.buffer(), &[1, 4, 2, 5, 3, 6]);
+        assert_eq!(channel.buffer().as_ptr(), ptr);
+
+        let empty = DMatrix::<u8>::zeros(0, 3);
+        assert!(ImageChannel::try_from_matrix(empty, MatrixOrder::RowMajor).is_err());
+    }
+}
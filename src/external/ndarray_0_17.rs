@@ -0,0 +1,219 @@
+use std::num::{NonZeroU8, NonZeroU32};
+
+use ndarray::{Array3, ArrayView3, ArrayViewMut3, Ix3, ShapeBuilder, StrideShape};
+
+use crate::{
+    Image, ImageChannel, PixelType, PixelTypePrimitive, channel::UnsafeImageChannel,
+    pixel::RuntimePixelType, shared_vec::plane_stride,
+};
+
+#[derive(Debug, thiserror::Error)]
+#[error("Cannot convert the array into an ImageChannel: {reason}")]
+#[non_exhaustive]
+pub struct IncompatibleArrayError<T> {
+    pub array: Array3<T>,
+    pub reason: IncompatibleArrayErrorReason,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[non_exhaustive]
+pub enum IncompatibleArrayErrorReason {
+    #[error("Height and width of {shape:?} must be within 1..=u32::MAX")]
+    InvalidDimensions { shape: [usize; 3] },
+    #[error("Expected {expected} elements per pixel, got {actual}")]
+    IncompatiblePixelElements { expected: NonZeroU8, actual: usize },
+}
+
+impl<TP: RuntimePixelType> ImageChannel<TP> {
+    /// Borrows the channel as `height × width × pixel_elements` array without copying. Row padding is skipped via strides.
+    #[must_use]
+    pub fn as_array_view(&self) -> ArrayView3<'_, TP::Primitive> {
+        unsafe { ArrayView3::from_shape_ptr(array_shape(&self.0), self.0.ptr) }
+    }
+
+    /// Like [`Self::as_array_view`], but copies shared buffers first (see [`ImageChannel::make_mut`])
+    pub fn as_array_view_mut(&mut self) -> ArrayViewMut3<'_, TP::Primitive> {
+        unsafe {
+            (self.0.vtable.make_mut)(&mut self.0);
+            ArrayViewMut3::from_shape_ptr(array_shape(&self.0), self.0.ptr.cast_mut())
+        }
+    }
+}
+
+fn array_shape<T>(channel: &UnsafeImageChannel<T>) -> StrideShape<Ix3> {
+    let pixel_elements = channel.pixel_elements.get() as usize;
+    (
+        channel.height.get() as usize,
+        channel.width.get() as usize,
+        pixel_elements,
+    )
+        .strides((channel.stride, pixel_elements, 1))
+}
+
+impl<T: PixelTypePrimitive, const CHANNELS: usize> Image<T, CHANNELS> {
+    /// Borrows all channels as `channels × height × width` array without copying
+    ///
+    /// Returns `None` unless the channels are stored in one buffer in ascending order with the same distance
+    /// (e.g. images created by [`Image::new_vec`] or [`ImageChannel::new_shared_vec_flat`])
+    #[must_use]
+    pub fn as_array_view(&self) -> Option<ArrayView3<'_, T>> {
+        let plane_stride = plane_stride(&self.0)?;
+        let first = &self.0[0].0;
+        let shape = (
+            CHANNELS,
+            first.height.get() as usize,
+            first.width.get() as usize,
+        )
+            .strides((plane_stride, first.stride, 1));
+        // All channels are within the same allocation, which is kept alive by `self`
+        Some(unsafe { ArrayView3::from_shape_ptr(shape, first.ptr) })
+    }
+
+    /// Mutably borrows every channel as `height × width × 1` array. Shared buffers are copied first (see [`ImageChannel::make_mut`])
+    pub fn as_array_views_mut(&mut self) -> [ArrayViewMut3<'_, T>; CHANNELS] {
+        self.0.each_mut().map(ImageChannel::as_array_view_mut)
+    }
+}
+
+/// Reuses the allocation of standard layout arrays, which don't start at an offset. Other arrays are copied.
+impl<TP: PixelType> TryFrom<Array3<TP::Primitive>> for ImageChannel<TP> {
+    type Error = IncompatibleArrayError<TP::Primitive>;
+
+    fn try_from(array: Array3<TP::Primitive>) -> Result<Self, Self::Error> {
+        let (height, width, pixel_elements) = array.dim();
+        let dimensions = u32::try_from(width)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .zip(u32::try_from(height).ok().and_then(NonZeroU32::new));
+        let Some((width_u32, height_u32)) = dimensions else {
+            return Err(IncompatibleArrayError {
+                array,
+                reason: IncompatibleArrayErrorReason::InvalidDimensions {
+                    shape: [height, width, pixel_elements],
+                },
+            });
+        };
+        if pixel_elements != TP::ELEMENTS.get() as usize {
+            return Err(IncompatibleArrayError {
+                array,
+                reason: IncompatibleArrayErrorReason::IncompatiblePixelElements {
+                    expected: TP::ELEMENTS,
+                    actual: pixel_elements,
+                },
+            });
+        }
+
+        let len = height * width * pixel_elements;
+        let data = if array.is_standard_layout() {
+            match array.into_raw_vec_and_offset() {
+                (vec, Some(0) | None) if vec.len() == len => vec,
+                (vec, offset) => vec[offset.unwrap_or(0)..][..len].to_vec(),
+            }
+        } else {
+            array.iter().copied().collect()
+        };
+        Ok(ImageChannel(UnsafeImageChannel::new_vec(
+            data,
+            width_u32,
+            height_u32,
+            TP::ELEMENTS,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use ndarray::{Array3, s};
+
+    use super::IncompatibleArrayErrorReason;
+    use crate::{Image, ImageChannel};
+
+    #[test]
+    fn channel_view_skips_padding() {
+        let three = NonZeroU32::new(3).unwrap();
+        let channel =
+            ImageChannel::<[u8; 2]>::new_vec((0..9).map(|x| [x, x + 100]).collect(), three, three);
+        let view = channel.view(1, 1, NonZeroU32::MIN, NonZeroU32::new(2).unwrap());
+        let view = view.unwrap();
+        let array = view.as_array_view();
+        assert_eq!(array.dim(), (2, 1, 2));
+        assert_eq!(array[[0, 0, 0]], 4);
+        assert_eq!(array[[1, 0, 1]], 107);
+        assert_eq!(array.as_ptr(), channel.buffer()[4].as_ptr());
+    }
+
+    #[test]
+    fn mutable_view_copies_shared_buffer() {
+        let mut channel =
+            ImageChannel::<u16>::new_vec(vec![1, 2], NonZeroU32::new(2).unwrap(), NonZeroU32::MIN);
+        let clone = channel.clone();
+        channel.as_array_view_mut()[[0, 1, 0]] = 42;
+        assert_eq!(channel.buffer(), &[1, 42]);
+        assert_eq!(clone.buffer(), &[1, 2]);
+    }
+
+    #[test]
+    fn planar_image_view() {
+        let image = Image::<u8, 3>::new_vec(
+            (0..6).collect(),
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let array = image.as_array_view().unwrap();
+        assert_eq!(array.dim(), (3, 1, 2));
+        assert_eq!(array.slice(s![.., 0, 1]).to_vec(), vec![1, 3, 5]);
+
+        let separate = Image::<u8, 2>::try_from([
+            ImageChannel::new_vec(vec![0], NonZeroU32::MIN, NonZeroU32::MIN),
+            ImageChannel::new_vec(vec![1], NonZeroU32::MIN, NonZeroU32::MIN),
+        ])
+        .unwrap();
+        assert!(separate.as_array_view().is_none());
+
+        let mut image = image;
+        for mut view in image.as_array_views_mut() {
+            view[[0, 0, 0]] = 9;
+        }
+        assert_eq!(image.buffers(), [[9, 1], [9, 3], [9, 5]]);
+    }
+
+    #[test]
+    fn from_standard_layout_array_reuses_allocation() {
+        let array = Array3::from_shape_vec((2, 1, 3), (0u8..6).collect()).unwrap();
+        let ptr = array.as_ptr();
+        let channel = ImageChannel::<[u8; 3]>::try_from(array).unwrap();
+        assert_eq!(channel.buffer(), &[[0, 1, 2], [3, 4, 5]]);
+        assert_eq!(channel.buffer().as_ptr().cast(), ptr);
+    }
+
+    #[test]
+    fn from_non_standard_layout_array_copies() {
+        let array = Array3::from_shape_vec((2, 2, 1), vec![0u8, 1, 2, 3]).unwrap();
+        let transposed = array.clone().permuted_axes([1, 0, 2]);
+        let channel = ImageChannel::<u8>::try_from(transposed).unwrap();
+        assert_eq!(channel.buffer(), &[0, 2, 1, 3]);
+
+        let sliced = array.slice_move(s![1.., .., ..]);
+        let channel = ImageChannel::<u8>::try_from(sliced).unwrap();
+        assert_eq!(channel.buffer(), &[2, 3]);
+    }
+
+    #[test]
+    fn reject_incompatible_arrays() {
+        let error = ImageChannel::<[u8; 2]>::try_from(Array3::<u8>::zeros((1, 1, 3))).unwrap_err();
+        assert_eq!(
+            error.reason,
+            IncompatibleArrayErrorReason::IncompatiblePixelElements {
+                expected: 2.try_into().unwrap(),
+                actual: 3
+            }
+        );
+        let error = ImageChannel::<u8>::try_from(Array3::<u8>::zeros((0, 1, 1))).unwrap_err();
+        assert!(matches!(
+            error.reason,
+            IncompatibleArrayErrorReason::InvalidDimensions { .. }
+        ));
+    }
+}
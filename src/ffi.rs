@@ -0,0 +1,711 @@
+//! C interface, which is declared in `include/imbuf.h`
+//!
+//! Channels are passed as [`UnsafeImageChannel`], whose layout is part of the header, so C can read pixels and call the vtable directly.
+//! Dynamic images are opaque pointers. Functions are provided for every primitive except `f16` and `bf16` (e.g. `imbuf_channel_u8_clone`).
+//!
+//! Channels and images must only be dropped once and must not be used afterwards.
+use std::{
+    ffi::c_void,
+    num::{NonZeroU8, NonZeroU32},
+    ptr::NonNull,
+    sync::Arc,
+};
+
+use crate::{
+    DynamicImage, DynamicImageChannel, DynamicSize, ImageChannel, PixelTypePrimitive,
+    channel::{UnsafeImageChannel, calc_pixel_len_flat},
+};
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImbufStatus {
+    Ok = 0,
+    NullPointer = 1,
+    /// Width, height or pixel elements are 0 or the stride is smaller than a row
+    InvalidLayout = 2,
+    IndexOutOfBounds = 3,
+    /// The channel has a different primitive than requested
+    IncompatiblePrimitive = 4,
+    /// The channel was created by a build with a different [`crate::ImageChannelAbi`]
+    IncompatibleAbi = 5,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImbufPrimitive {
+    U8 = 0,
+    I8 = 1,
+    U16 = 2,
+    I16 = 3,
+    U32 = 4,
+    I32 = 5,
+    F32 = 6,
+    F64 = 7,
+    F16 = 8,
+    BF16 = 9,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImbufChannelInfo {
+    pub primitive: ImbufPrimitive,
+    pub width: u32,
+    pub height: u32,
+    /// Distance between the start of two consecutive rows in primitives
+    pub stride: usize,
+    pub pixel_elements: u8,
+}
+
+/// Called once the last channel referencing external memory is dropped
+pub type ImbufReleaseFn = unsafe extern "C" fn(context: *mut c_void);
+
+/// Owner of memory, which was handed over by C
+struct ExternalOwner {
+    release: Option<ImbufReleaseFn>,
+    context: *mut c_void,
+}
+
+// The caller of `imbuf_channel_*_from_external` guarantees, that `release` may be called from any thread
+unsafe impl Send for ExternalOwner {}
+unsafe impl Sync for ExternalOwner {}
+
+impl Drop for ExternalOwner {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self.context) };
+        }
+    }
+}
+
+fn check_layout(
+    width: u32,
+    height: u32,
+    stride: usize,
+    pixel_elements: u8,
+) -> Result<(NonZeroU32, NonZeroU32, NonZeroU8), ImbufStatus> {
+    let (Some(width), Some(height), Some(pixel_elements)) = (
+        NonZeroU32::new(width),
+        NonZeroU32::new(height),
+        NonZeroU8::new(pixel_elements),
+    ) else {
+        return Err(ImbufStatus::InvalidLayout);
+    };
+    if stride < calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements) {
+        return Err(ImbufStatus::InvalidLayout);
+    }
+    Ok((width, height, pixel_elements))
+}
+
+fn primitive_of(channel: &DynamicImageChannel) -> ImbufPrimitive {
+    match channel {
+        DynamicImageChannel::U8(_) => ImbufPrimitive::U8,
+        DynamicImageChannel::I8(_) => ImbufPrimitive::I8,
+        DynamicImageChannel::U16(_) => ImbufPrimitive::U16,
+        DynamicImageChannel::I16(_) => ImbufPrimitive::I16,
+        DynamicImageChannel::U32(_) => ImbufPrimitive::U32,
+        DynamicImageChannel::I32(_) => ImbufPrimitive::I32,
+        DynamicImageChannel::F32(_) => ImbufPrimitive::F32,
+        DynamicImageChannel::F64(_) => ImbufPrimitive::F64,
+        #[cfg(feature = "half")]
+        DynamicImageChannel::F16(_) => ImbufPrimitive::F16,
+        #[cfg(feature = "half")]
+        DynamicImageChannel::BF16(_) => ImbufPrimitive::BF16,
+    }
+}
+
+/// Reads a channel, which might have been created by another build, after checking its [`crate::ImageChannelAbi`]
+///
+/// # Safety
+/// See [`UnsafeImageChannel::try_import`]
+unsafe fn import<T: PixelTypePrimitive>(
+    channel: *mut UnsafeImageChannel<T>,
+) -> Result<UnsafeImageChannel<T>, ImbufStatus> {
+    let channel = NonNull::new(channel).ok_or(ImbufStatus::NullPointer)?;
+    unsafe { UnsafeImageChannel::try_import(channel) }.map_err(|_| ImbufStatus::IncompatibleAbi)
+}
+
+macro_rules! impl_ffi_channel {
+    (
+        $t:ty,
+        $from_copy:ident,
+        $from_external:ident,
+        $clone:ident,
+        $make_mut:ident,
+        $drop:ident,
+        $import:ident,
+        $image_new:ident,
+        $image_push:ident,
+        $image_get:ident
+    ) => {
+        /// Copies `height` rows of `width * pixel_elements` primitives, which are `stride` primitives apart
+        ///
+        /// # Safety
+        /// `data` must be valid for reading these rows and `out` must be valid for writing
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $from_copy(
+            data: *const $t,
+            width: u32,
+            height: u32,
+            stride: usize,
+            pixel_elements: u8,
+            out: *mut UnsafeImageChannel<$t>,
+        ) -> ImbufStatus {
+            if data.is_null() || out.is_null() {
+                return ImbufStatus::NullPointer;
+            }
+            let (width, height, pixel_elements) =
+                match check_layout(width, height, stride, pixel_elements) {
+                    Ok(x) => x,
+                    Err(e) => return e,
+                };
+            let row_len = calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements);
+            let data = (0..height.get() as usize)
+                .flat_map(|y| unsafe { std::slice::from_raw_parts(data.add(y * stride), row_len) })
+                .copied()
+                .collect();
+            let channel = UnsafeImageChannel::new_vec(data, width, height, pixel_elements);
+            unsafe { out.write(channel) };
+            ImbufStatus::Ok
+        }
+
+        /// Creates a channel without copying. `release(context)` is called once the last clone is dropped, if `release` is not NULL.
+        /// `make_mut` always copies the pixels, because the memory might be read-only.
+        ///
+        /// # Safety
+        /// `data` must be valid for reading `height` rows of `width * pixel_elements` primitives, which are `stride` primitives apart, until `release` is called.
+        /// `release` must be callable from any thread. `out` must be valid for writing.
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $from_external(
+            data: *const $t,
+            width: u32,
+            height: u32,
+            stride: usize,
+            pixel_elements: u8,
+            release: Option<ImbufReleaseFn>,
+            context: *mut c_void,
+            out: *mut UnsafeImageChannel<$t>,
+        ) -> ImbufStatus {
+            if data.is_null() || out.is_null() {
+                return ImbufStatus::NullPointer;
+            }
+            let (width, height, pixel_elements) =
+                match check_layout(width, height, stride, pixel_elements) {
+                    Ok(x) => x,
+                    Err(e) => return e,
+                };
+            let owner = Arc::new(ExternalOwner { release, context });
+            let channel = unsafe {
+                UnsafeImageChannel::new_arc_owner(
+                    data,
+                    width,
+                    height,
+                    stride,
+                    pixel_elements,
+                    owner,
+                )
+            };
+            unsafe { out.write(channel) };
+            ImbufStatus::Ok
+        }
+
+        /// Clones the channel through its vtable, which usually shares the pixels
+        ///
+        /// # Safety
+        /// `channel` must point to a valid channel and `out` must be valid for writing
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $clone(
+            channel: *const UnsafeImageChannel<$t>,
+            out: *mut UnsafeImageChannel<$t>,
+        ) -> ImbufStatus {
+            let Some(channel) = (unsafe { channel.as_ref() }) else {
+                return ImbufStatus::NullPointer;
+            };
+            if out.is_null() {
+                return ImbufStatus::NullPointer;
+            }
+            unsafe { out.write((channel.vtable.clone)(channel)) };
+            ImbufStatus::Ok
+        }
+
+        /// Ensures exclusive access to the pixels and returns a pointer to the first one. Returns NULL, if `channel` is NULL.
+        /// The layout of the channel might change (e.g. when shared pixels are copied), so `stride` has to be read afterwards.
+        ///
+        /// # Safety
+        /// `channel` must point to a valid channel
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $make_mut(channel: *mut UnsafeImageChannel<$t>) -> *mut $t {
+            let Some(channel) = (unsafe { channel.as_mut() }) else {
+                return std::ptr::null_mut();
+            };
+            unsafe { (channel.vtable.make_mut)(channel) };
+            channel.ptr.cast_mut()
+        }
+
+        /// Sets `ptr` to NULL afterwards, which marks the channel as dropped. Does nothing, if `channel` or its `ptr` is NULL.
+        ///
+        /// # Safety
+        /// `channel` must be NULL or point to a valid or dropped channel
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $drop(channel: *mut UnsafeImageChannel<$t>) {
+            if let Some(channel) = unsafe { channel.as_mut() } {
+                if !channel.ptr.is_null() {
+                    unsafe { (channel.vtable.drop)(channel) };
+                    channel.ptr = std::ptr::null();
+                }
+            }
+        }
+
+        /// Moves a channel, which might have been created by another build (e.g. a plugin), to `out` after checking its [`crate::ImageChannelAbi`].
+        /// On failure, `channel` is left untouched and must be released by its creator.
+        ///
+        /// # Safety
+        /// `channel` must point to a valid channel, which isn't used afterwards on success, and `out` must be valid for writing
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $import(
+            channel: *mut UnsafeImageChannel<$t>,
+            out: *mut UnsafeImageChannel<$t>,
+        ) -> ImbufStatus {
+            if out.is_null() {
+                return ImbufStatus::NullPointer;
+            }
+            match unsafe { import(channel) } {
+                Ok(channel) => {
+                    unsafe { out.write(channel) };
+                    ImbufStatus::Ok
+                }
+                Err(e) => e,
+            }
+        }
+
+        /// Writes an image to `out`, which takes ownership of `channel`. Free it with [`imbuf_dynamic_image_drop`].
+        /// Like `imbuf_channel_<p>_import`, the channel might have been created by another build. On failure, it is left untouched.
+        ///
+        /// # Safety
+        /// `channel` must point to a valid channel, which isn't used afterwards on success, and `out` must be valid for writing
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $image_new(
+            channel: *mut UnsafeImageChannel<$t>,
+            out: *mut *mut DynamicImage,
+        ) -> ImbufStatus {
+            if out.is_null() {
+                return ImbufStatus::NullPointer;
+            }
+            match unsafe { import(channel) } {
+                Ok(channel) => {
+                    let channel =
+                        <$t>::into_runtime_channel(ImageChannel::<DynamicSize<$t>>(channel));
+                    let image = Box::new(DynamicImage::from_channels(channel, []));
+                    unsafe { out.write(Box::into_raw(image)) };
+                    ImbufStatus::Ok
+                }
+                Err(e) => e,
+            }
+        }
+
+        /// Appends `channel`, which is taken ownership of on success.
+        /// Like `imbuf_channel_<p>_import`, the channel might have been created by another build. On failure, it is left untouched.
+        ///
+        /// # Safety
+        /// `image` must be NULL or a valid image. `channel` must point to a valid channel, which isn't used afterwards on success
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $image_push(
+            image: *mut DynamicImage,
+            channel: *mut UnsafeImageChannel<$t>,
+        ) -> ImbufStatus {
+            let Some(image) = (unsafe { image.as_mut() }) else {
+                return ImbufStatus::NullPointer;
+            };
+            match unsafe { import(channel) } {
+                Ok(channel) => {
+                    image.push(<$t>::into_runtime_channel(ImageChannel::<DynamicSize<$t>>(
+                        channel,
+                    )));
+                    ImbufStatus::Ok
+                }
+                Err(e) => e,
+            }
+        }
+
+        /// Writes a clone of the channel at `index` to `out`, which has to be dropped by the caller
+        ///
+        /// # Safety
+        /// `image` must be NULL or a valid image and `out` must be valid for writing
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn $image_get(
+            image: *const DynamicImage,
+            index: usize,
+            out: *mut UnsafeImageChannel<$t>,
+        ) -> ImbufStatus {
+            let Some(image) = (unsafe { image.as_ref() }) else {
+                return ImbufStatus::NullPointer;
+            };
+            if out.is_null() {
+                return ImbufStatus::NullPointer;
+            }
+            let Some(channel) = image.get(index) else {
+                return ImbufStatus::IndexOutOfBounds;
+            };
+            let Some(channel) = <$t>::try_from_dynamic_image_ref(channel) else {
+                return ImbufStatus::IncompatiblePrimitive;
+            };
+            unsafe { out.write(channel.clone().0) };
+            ImbufStatus::Ok
+        }
+    };
+}
+
+impl_ffi_channel!(
+    u8,
+    imbuf_channel_u8_from_copy,
+    imbuf_channel_u8_from_external,
+    imbuf_channel_u8_clone,
+    imbuf_channel_u8_make_mut,
+    imbuf_channel_u8_drop,
+    imbuf_channel_u8_import,
+    imbuf_dynamic_image_new_u8,
+    imbuf_dynamic_image_push_u8,
+    imbuf_dynamic_image_get_u8
+);
+impl_ffi_channel!(
+    i8,
+    imbuf_channel_i8_from_copy,
+    imbuf_channel_i8_from_external,
+    imbuf_channel_i8_clone,
+    imbuf_channel_i8_make_mut,
+    imbuf_channel_i8_drop,
+    imbuf_channel_i8_import,
+    imbuf_dynamic_image_new_i8,
+    imbuf_dynamic_image_push_i8,
+    imbuf_dynamic_image_get_i8
+);
+impl_ffi_channel!(
+    u16,
+    imbuf_channel_u16_from_copy,
+    imbuf_channel_u16_from_external,
+    imbuf_channel_u16_clone,
+    imbuf_channel_u16_make_mut,
+    imbuf_channel_u16_drop,
+    imbuf_channel_u16_import,
+    imbuf_dynamic_image_new_u16,
+    imbuf_dynamic_image_push_u16,
+    imbuf_dynamic_image_get_u16
+);
+impl_ffi_channel!(
+    i16,
+    imbuf_channel_i16_from_copy,
+    imbuf_channel_i16_from_external,
+    imbuf_channel_i16_clone,
+    imbuf_channel_i16_make_mut,
+    imbuf_channel_i16_drop,
+    imbuf_channel_i16_import,
+    imbuf_dynamic_image_new_i16,
+    imbuf_dynamic_image_push_i16,
+    imbuf_dynamic_image_get_i16
+);
+impl_ffi_channel!(
+    u32,
+    imbuf_channel_u32_from_copy,
+    imbuf_channel_u32_from_external,
+    imbuf_channel_u32_clone,
+    imbuf_channel_u32_make_mut,
+    imbuf_channel_u32_drop,
+    imbuf_channel_u32_import,
+    imbuf_dynamic_image_new_u32,
+    imbuf_dynamic_image_push_u32,
+    imbuf_dynamic_image_get_u32
+);
+impl_ffi_channel!(
+    i32,
+    imbuf_channel_i32_from_copy,
+    imbuf_channel_i32_from_external,
+    imbuf_channel_i32_clone,
+    imbuf_channel_i32_make_mut,
+    imbuf_channel_i32_drop,
+    imbuf_channel_i32_import,
+    imbuf_dynamic_image_new_i32,
+    imbuf_dynamic_image_push_i32,
+    imbuf_dynamic_image_get_i32
+);
+impl_ffi_channel!(
+    f32,
+    imbuf_channel_f32_from_copy,
+    imbuf_channel_f32_from_external,
+    imbuf_channel_f32_clone,
+    imbuf_channel_f32_make_mut,
+    imbuf_channel_f32_drop,
+    imbuf_channel_f32_import,
+    imbuf_dynamic_image_new_f32,
+    imbuf_dynamic_image_push_f32,
+    imbuf_dynamic_image_get_f32
+);
+impl_ffi_channel!(
+    f64,
+    imbuf_channel_f64_from_copy,
+    imbuf_channel_f64_from_external,
+    imbuf_channel_f64_clone,
+    imbuf_channel_f64_make_mut,
+    imbuf_channel_f64_drop,
+    imbuf_channel_f64_import,
+    imbuf_dynamic_image_new_f64,
+    imbuf_dynamic_image_push_f64,
+    imbuf_dynamic_image_get_f64
+);
+
+/// Returns 0, if `image` is NULL
+///
+/// # Safety
+/// `image` must be NULL or a valid image
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imbuf_dynamic_image_len(image: *const DynamicImage) -> usize {
+    unsafe { image.as_ref() }.map_or(0, |image| image.len().get())
+}
+
+/// # Safety
+/// `image` must be NULL or a valid image and `out` must be valid for writing
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imbuf_dynamic_image_channel_info(
+    image: *const DynamicImage,
+    index: usize,
+    out: *mut ImbufChannelInfo,
+) -> ImbufStatus {
+    let Some(image) = (unsafe { image.as_ref() }) else {
+        return ImbufStatus::NullPointer;
+    };
+    if out.is_null() {
+        return ImbufStatus::NullPointer;
+    }
+    let Some(channel) = image.get(index) else {
+        return ImbufStatus::IndexOutOfBounds;
+    };
+    let info = ImbufChannelInfo {
+        primitive: primitive_of(channel),
+        width: channel.width().get(),
+        height: channel.height().get(),
+        stride: channel.stride(),
+        pixel_elements: channel.pixel_elements().get(),
+    };
+    unsafe { out.write(info) };
+    ImbufStatus::Ok
+}
+
+/// Clones all channels through their vtable, which usually shares the pixels. Returns NULL, if `image` is NULL.
+///
+/// # Safety
+/// `image` must be NULL or a valid image
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imbuf_dynamic_image_clone(
+    image: *const DynamicImage,
+) -> *mut DynamicImage {
+    unsafe { image.as_ref() }.map_or(std::ptr::null_mut(), |image| {
+        Box::into_raw(Box::new(image.clone()))
+    })
+}
+
+/// Does nothing, if `image` is NULL
+///
+/// # Safety
+/// `image` must be NULL or a valid image, which isn't used afterwards
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn imbuf_dynamic_image_drop(image: *mut DynamicImage) {
+    if !image.is_null() {
+        drop(unsafe { Box::from_raw(image) });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        ffi::c_void,
+        mem::MaybeUninit,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use super::*;
+
+    #[test]
+    fn copy_strided_rows() {
+        let data = [1u16, 2, 0, 3, 4, 0];
+        let mut channel = MaybeUninit::uninit();
+        let status =
+            unsafe { imbuf_channel_u16_from_copy(data.as_ptr(), 2, 2, 3, 1, channel.as_mut_ptr()) };
+        assert_eq!(status, ImbufStatus::Ok);
+        let channel = ImageChannel::<u16>(unsafe { channel.assume_init() });
+        assert_eq!(channel.buffer(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn reject_invalid_layouts() {
+        let data = [0u8; 4];
+        let mut channel = MaybeUninit::uninit();
+        for (width, height, stride, pixel_elements) in [(0, 1, 1, 1), (1, 1, 1, 0), (2, 1, 3, 2)] {
+            let status = unsafe {
+                imbuf_channel_u8_from_copy(
+                    data.as_ptr(),
+                    width,
+                    height,
+                    stride,
+                    pixel_elements,
+                    channel.as_mut_ptr(),
+                )
+            };
+            assert_eq!(status, ImbufStatus::InvalidLayout);
+        }
+        let status = unsafe {
+            imbuf_channel_u8_from_copy(std::ptr::null(), 1, 1, 1, 1, channel.as_mut_ptr())
+        };
+        assert_eq!(status, ImbufStatus::NullPointer);
+    }
+
+    #[test]
+    fn external_memory_is_released_once() {
+        unsafe extern "C" fn release(context: *mut c_void) {
+            unsafe { &*context.cast::<AtomicUsize>() }.fetch_add(1, Ordering::Relaxed);
+        }
+        let released = AtomicUsize::new(0);
+        let data = [1.0f32, 2.0];
+        let mut channel = MaybeUninit::uninit();
+        let status = unsafe {
+            imbuf_channel_f32_from_external(
+                data.as_ptr(),
+                1,
+                1,
+                2,
+                2,
+                Some(release),
+                (&raw const released).cast_mut().cast(),
+                channel.as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, ImbufStatus::Ok);
+        let mut channel = unsafe { channel.assume_init() };
+        let mut clone = MaybeUninit::uninit();
+        assert_eq!(
+            unsafe { imbuf_channel_f32_clone(&raw const channel, clone.as_mut_ptr()) },
+            ImbufStatus::Ok
+        );
+        let mut clone = unsafe { clone.assume_init() };
+        assert_eq!(clone.ptr, data.as_ptr());
+
+        let ptr = unsafe { imbuf_channel_f32_make_mut(&raw mut clone) };
+        assert_ne!(ptr.cast_const(), data.as_ptr());
+        unsafe { imbuf_channel_f32_drop(&raw mut clone) };
+        assert_eq!(released.load(Ordering::Relaxed), 0);
+        unsafe { imbuf_channel_f32_drop(&raw mut channel) };
+        assert_eq!(released.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn import_checks_abi() {
+        let mut channel = MaybeUninit::uninit();
+        unsafe { imbuf_channel_u8_from_copy([1].as_ptr(), 1, 1, 1, 1, channel.as_mut_ptr()) };
+        let mut channel = unsafe { channel.assume_init() };
+        let mut imported = MaybeUninit::uninit();
+        let status = unsafe { imbuf_channel_u8_import(&raw mut channel, imported.as_mut_ptr()) };
+        assert_eq!(status, ImbufStatus::Ok);
+        std::mem::forget(channel);
+        let mut imported = unsafe { imported.assume_init() };
+        unsafe { imbuf_channel_u8_drop(&raw mut imported) };
+
+        let mut foreign = [0u32; 7];
+        let foreign_vtable = std::ptr::from_mut(&mut foreign);
+        let mut channel = [foreign_vtable as usize, 0, 0, 0, 0, 0];
+        let status = unsafe {
+            imbuf_channel_u8_import(
+                channel.as_mut_ptr().cast(),
+                MaybeUninit::uninit().as_mut_ptr(),
+            )
+        };
+        assert_eq!(status, ImbufStatus::IncompatibleAbi);
+
+        let mut image = std::ptr::null_mut();
+        let status =
+            unsafe { imbuf_dynamic_image_new_u8(channel.as_mut_ptr().cast(), &raw mut image) };
+        assert_eq!(status, ImbufStatus::IncompatibleAbi);
+        assert!(image.is_null());
+
+        let mut valid = MaybeUninit::uninit();
+        unsafe { imbuf_channel_u8_from_copy([1].as_ptr(), 1, 1, 1, 1, valid.as_mut_ptr()) };
+        let status = unsafe { imbuf_dynamic_image_new_u8(valid.as_mut_ptr(), &raw mut image) };
+        assert_eq!(status, ImbufStatus::Ok);
+        let status = unsafe { imbuf_dynamic_image_push_u8(image, channel.as_mut_ptr().cast()) };
+        assert_eq!(status, ImbufStatus::IncompatibleAbi);
+        assert_eq!(unsafe { imbuf_dynamic_image_len(image) }, 1);
+        unsafe { imbuf_dynamic_image_drop(image) };
+    }
+
+    #[test]
+    fn dynamic_image() {
+        let data = [1u8, 2, 3];
+        let mut channel = MaybeUninit::uninit();
+        unsafe { imbuf_channel_u8_from_copy(data.as_ptr(), 3, 1, 3, 1, channel.as_mut_ptr()) };
+        let mut image = std::ptr::null_mut();
+        let status = unsafe { imbuf_dynamic_image_new_u8(channel.as_mut_ptr(), &raw mut image) };
+        assert_eq!(status, ImbufStatus::Ok);
+        let mut other = MaybeUninit::uninit();
+        unsafe { imbuf_channel_i32_from_copy([7].as_ptr(), 1, 1, 1, 1, other.as_mut_ptr()) };
+        let status = unsafe { imbuf_dynamic_image_push_i32(image, other.as_mut_ptr()) };
+        assert_eq!(status, ImbufStatus::Ok);
+        assert_eq!(unsafe { imbuf_dynamic_image_len(image) }, 2);
+
+        let mut info = MaybeUninit::uninit();
+        let status = unsafe { imbuf_dynamic_image_channel_info(image, 1, info.as_mut_ptr()) };
+        assert_eq!(status, ImbufStatus::Ok);
+        assert_eq!(
+            unsafe { info.assume_init() },
+            ImbufChannelInfo {
+                primitive: ImbufPrimitive::I32,
+                width: 1,
+                height: 1,
+                stride: 1,
+                pixel_elements: 1
+            }
+        );
+
+        let clone = unsafe { imbuf_dynamic_image_clone(image) };
+        unsafe { imbuf_dynamic_image_drop(image) };
+        let mut channel = MaybeUninit::uninit();
+        assert_eq!(
+            unsafe { imbuf_dynamic_image_get_u8(clone, 1, channel.as_mut_ptr()) },
+            ImbufStatus::IncompatiblePrimitive
+        );
+        assert_eq!(
+            unsafe { imbuf_dynamic_image_get_u8(clone, 2, channel.as_mut_ptr()) },
+            ImbufStatus::IndexOutOfBounds
+        );
+        assert_eq!(
+            unsafe { imbuf_dynamic_image_get_u8(clone, 0, channel.as_mut_ptr()) },
+            ImbufStatus::Ok
+        );
+        let channel = ImageChannel::<u8>(unsafe { channel.assume_init() });
+        unsafe { imbuf_dynamic_image_drop(clone) };
+        assert_eq!(channel.buffer(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn header_declares_all_functions() {
+        let header = include_str!("../include/imbuf.h");
+        let mut exported = include_str!("ffi.rs")
+            .split(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .filter(|word| word.starts_with("imbuf_") && !word.ends_with('_'))
+            .collect::<Vec<_>>();
+        exported.sort_unstable();
+        exported.dedup();
+        assert_eq!(exported.len(), 8 * 9 + 4);
+        assert!(header.contains(&format!(
+            "#define IMBUF_CHANNEL_ABI_VERSION {}\n",
+            crate::IMAGE_CHANNEL_ABI_VERSION
+        )));
+        for name in exported {
+            assert!(header.contains(&format!("{name}(")), "{name} is missing");
+        }
+        let abi = header
+            .split("typedef struct ImbufChannelAbi {")
+            .nth(1)
+            .and_then(|rest| rest.split('}').next())
+            .unwrap();
+        assert_eq!(
+            abi.matches("uint32_t ").count() * 4,
+            size_of::<crate::ImageChannelAbi>()
+        );
+    }
+}
@@ -6,8 +6,11 @@ use std::{
 };
 
 use crate::{
-    IncompatibleImageError,
-    channel::{BorrowableImageChannel, ImageChannel, calc_pixel_len_flat},
+    IncompatibleImageError, Metadata,
+    channel::{
+        BorrowableImageChannel, ImageChannel, IncompatibleBufferSizeError, calc_pixel_len_flat,
+        checked_calc_pixel_len_flat,
+    },
     dynamic::IncompatibleImageErrorReason,
     pixel::{PixelType, PixelTypePrimitive, RuntimePixelType},
     unwrap_usize_to_nonzero_u8,
@@ -20,8 +23,7 @@ pub type ImageMut<'a, T, const CHANNELS: usize> =
 
 /// Represents a image, where all channels share the same width, height. You usually want to use its typedef versions [`Image`], [`ImageRef`], [`ImageMut`] instead.
 #[derive(Clone)]
-#[repr(transparent)]
-pub struct ImageChannels<T>(T);
+pub struct ImageChannels<T>(pub(crate) T, pub(crate) Metadata);
 
 impl<T: BorrowableImageChannel, const CHANNELS: usize> PartialEq for ImageChannels<[T; CHANNELS]> {
     fn eq(&self, other: &Self) -> bool {
@@ -37,12 +39,27 @@ impl<const CHANNELS: usize, T: PixelType> Image<T, CHANNELS> {
     /// # Panics
     /// Panics if the buffer size is not compatible with the width and height.
     #[must_use]
-    pub fn new_vec_flat(mut input: Vec<T::Primitive>, width: NonZeroU32, height: NonZeroU32) -> Self
-    where
-        T: PixelType,
-    {
+    pub fn new_vec_flat(input: Vec<T::Primitive>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        match Self::try_new_vec_flat(input, width, height) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_vec_flat(
+        input: Vec<T::Primitive>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Vec<T::Primitive>>> {
         let pixel_elements = T::ELEMENTS.get() as usize;
-        assert_eq!(input.len() % pixel_elements, 0, "Incompatible Buffer-Size");
+        let mut input = IncompatibleBufferSizeError::check(
+            input,
+            Self::checked_calc_len_flat(width, height)
+                .and_then(|len| len.checked_mul(pixel_elements)),
+            Vec::len,
+        )?;
 
         let cap = input.capacity() / pixel_elements;
         let len = input.len() / pixel_elements;
@@ -50,32 +67,38 @@ impl<const CHANNELS: usize, T: PixelType> Image<T, CHANNELS> {
         std::mem::forget(input);
         let cast_input = unsafe { Vec::from_raw_parts(ptr, len, cap) };
 
-        Self::new_vec(cast_input, width, height)
+        Ok(Self::new_vec(cast_input, width, height))
     }
 
     /// # Panics
     /// Panics if the buffer size is not compatible with the width and height.
     #[must_use]
-    pub fn new_vec(mut input: Vec<T>, width: NonZeroU32, height: NonZeroU32) -> Self
-    where
-        T: Clone,
-    {
-        assert_eq!(
-            input.len(),
-            calc_pixel_len_flat(
-                width,
-                height,
-                const { unwrap_usize_to_nonzero_u8(CHANNELS) }
-            ),
-            "Incompatible Buffer-Size"
-        );
+    pub fn new_vec(input: Vec<T>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        match Self::try_new_vec(input, width, height) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
 
-        if CHANNELS == 1 {
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_vec(
+        input: Vec<T>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Vec<T>>> {
+        let mut input = IncompatibleBufferSizeError::check(
+            input,
+            Self::checked_calc_len_flat(width, height),
+            Vec::len,
+        )?;
+
+        Ok(if CHANNELS == 1 {
             let channel = ImageChannel::<T>::new_vec(input, width, height);
             unsafe {
                 let mut arr = std::mem::MaybeUninit::<[ImageChannel<T>; CHANNELS]>::uninit();
                 std::ptr::write(arr.as_mut_ptr().cast::<ImageChannel<T>>(), channel);
-                Self(arr.assume_init())
+                Self(arr.assume_init(), Metadata::new())
             }
         } else {
             let ptr = input.as_mut_ptr().cast::<T::Primitive>();
@@ -83,11 +106,20 @@ impl<const CHANNELS: usize, T: PixelType> Image<T, CHANNELS> {
             let cap = input.capacity() * T::ELEMENTS.get() as usize;
             std::mem::forget(input);
             let cast_input = unsafe { Vec::from_raw_parts(ptr, len, cap) };
-            Self(crate::shared_vec::create_shared_channels(
-                cast_input,
-                [(width, height); CHANNELS],
-            ))
-        }
+            Self(
+                ImageChannel::new_shared_vec_flat(cast_input, [(width, height); CHANNELS]),
+                Metadata::new(),
+            )
+        })
+    }
+
+    /// Number of `T` in all channels, `None` on overflow
+    const fn checked_calc_len_flat(width: NonZeroU32, height: NonZeroU32) -> Option<usize> {
+        checked_calc_pixel_len_flat(
+            width,
+            height,
+            const { unwrap_usize_to_nonzero_u8(CHANNELS) },
+        )
     }
 
     #[must_use]
@@ -95,29 +127,58 @@ impl<const CHANNELS: usize, T: PixelType> Image<T, CHANNELS> {
         self.0
     }
 
+    /// Concatenates all channels. Reuses the allocation, if the image was created by [`Self::new_vec`] or [`ImageChannel::new_shared_vec_flat`] and none of its channels is shared
     #[must_use]
-    pub fn into_vec(mut self) -> Vec<T>
+    pub fn into_vec(self) -> Vec<T>
     where
         T: Clone,
     {
+        let mut vec = self.into_vec_flat();
+        let pixel_elements = T::ELEMENTS.get() as usize;
+        if !vec.capacity().is_multiple_of(pixel_elements) {
+            vec = vec.into_boxed_slice().into_vec();
+        }
+
+        // Cast Vec<T::Primitive> back to Vec<T>
+        let ptr = vec.as_mut_ptr().cast::<T>();
+        let len = vec.len() / pixel_elements;
+        let cap = vec.capacity() / pixel_elements;
+        std::mem::forget(vec);
+
+        unsafe { Vec::from_raw_parts(ptr, len, cap) }
+    }
+
+    /// Like [`Self::into_vec`], but returns the primitives
+    #[must_use]
+    pub fn into_vec_flat(mut self) -> Vec<T::Primitive> {
         if CHANNELS == 1 {
-            // For single channel, use ImageChannel::into_vec which preserves pointer reuse
+            // For single channel, use ImageChannel::into_vec_flat which preserves pointer reuse
             // SAFETY: When CHANNELS == 1, we know the array has exactly one element
             let ch_ptr = self.0.as_mut_ptr();
             std::mem::forget(self);
             let channel = unsafe { std::ptr::read(ch_ptr) };
 
-            channel.into_vec()
+            channel.into_vec_flat()
         } else {
-            // For multiple channels, concatenate them
-            let mut result = Vec::with_capacity(self.len_per_channel() * CHANNELS);
-            for channel in self.0 {
-                result.extend_from_slice(channel.buffer());
+            let len = self.len_per_channel_flat() * CHANNELS;
+            match crate::shared_vec::try_reclaim_vec(self.0) {
+                Ok(vec) => vec,
+                Err(channels) => {
+                    // For multiple channels, concatenate them
+                    let mut result = Vec::with_capacity(len);
+                    for channel in channels {
+                        for row in channel.rows_flat() {
+                            result.extend_from_slice(row);
+                        }
+                    }
+                    result
+                }
             }
-            result
         }
     }
 
+    /// # Panics
+    /// Panics if the channel of `i` is not contiguous
     #[must_use]
     pub fn from_interleaved(i: &Image<[T; CHANNELS], 1>) -> Self
     where
@@ -134,27 +195,36 @@ impl<const CHANNELS: usize, T: PixelType> Image<T, CHANNELS> {
     where
         T: Copy,
     {
+        match Self::try_from_flat_interleaved(v, (width, height)) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// # Errors
+    /// Returns `v`, if the buffer size is not compatible with the width, height, and channel count.
+    pub fn try_from_flat_interleaved(
+        v: &[T],
+        (width, height): (NonZeroU32, NonZeroU32),
+    ) -> Result<Self, IncompatibleBufferSizeError<&[T]>>
+    where
+        T: Copy,
+    {
+        let v = IncompatibleBufferSizeError::check(
+            v,
+            Self::checked_calc_len_flat(width, height),
+            |v| v.len(),
+        )?;
         let len = width.get() as usize * height.get() as usize;
         if CHANNELS == 1 {
-            return Self::new_vec(v.to_vec(), width, height);
+            return Ok(Self::new_vec(v.to_vec(), width, height));
         }
 
-        assert_eq!(
-            v.len(),
-            calc_pixel_len_flat(
-                width,
-                height,
-                const { unwrap_usize_to_nonzero_u8(CHANNELS) }
-            ),
-            "Incompatible Buffer-Size"
-        );
-
         let mut write_buf_container = vec![std::mem::MaybeUninit::<T>::uninit(); len * CHANNELS];
 
         let mut next_read = 0;
 
-        let area = (width.get() * height.get()) as usize;
-        let write_offsets: [_; CHANNELS] = std::array::from_fn(|i| i * area);
+        let write_offsets: [_; CHANNELS] = std::array::from_fn(|i| i * len);
 
         for pixel in 0..len {
             for (i, write_offset) in write_offsets.iter().enumerate() {
@@ -169,7 +239,7 @@ impl<const CHANNELS: usize, T: PixelType> Image<T, CHANNELS> {
         let x = unsafe {
             std::mem::transmute::<Vec<std::mem::MaybeUninit<T>>, Vec<T>>(write_buf_container)
         };
-        Image::<T, CHANNELS>::new_vec(x, width, height)
+        Ok(Image::<T, CHANNELS>::new_vec(x, width, height))
     }
 }
 
@@ -181,7 +251,51 @@ impl<T: BorrowableImageChannel + BorrowMut<ImageChannel<T::Pixel>>, const CHANNE
         let mut iter = self.0.iter_mut();
         std::array::from_fn(|_| iter.next().unwrap().borrow_mut().make_mut())
     }
+
+    /// Returns the pixel of each channel at (`x`, `y`), or `None` if it is out of bounds
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_pixel_mut(&mut self, x: u32, y: u32) -> Option<[&mut T::Pixel; CHANNELS]> {
+        if x >= self.width().get() || y >= self.height().get() {
+            return None;
+        }
+        Some(self.0.each_mut().map(|channel| {
+            channel
+                .borrow_mut()
+                .get_pixel_mut(x, y)
+                .expect("All channels have the same dimensions")
+        }))
+    }
+
+    /// Iterates over the rows of all channels from top to bottom
+    #[allow(clippy::missing_panics_doc)]
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = [&mut [T::Pixel]; CHANNELS]> + '_ {
+        let height = self.height().get();
+        let mut rows = self
+            .0
+            .each_mut()
+            .map(|channel| channel.borrow_mut().rows_mut());
+        (0..height).map(move |_| {
+            rows.each_mut()
+                .map(|rows| rows.next().expect("All channels have the same height"))
+        })
+    }
 }
+impl<T> ImageChannels<T> {
+    /// Typed values, which are carried along with the pixels (see [`Metadata`])
+    ///
+    /// Borrowed images ([`ImageRef`], [`ImageMut`]) get a copy of the metadata, which is read-only.
+    #[must_use]
+    pub fn metadata(&self) -> &Metadata {
+        &self.1
+    }
+}
+
+impl<T: RuntimePixelType, const CHANNELS: usize> Image<T, CHANNELS> {
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.1
+    }
+}
+
 impl<T: BorrowableImageChannel, const CHANNELS: usize> ImageChannels<[T; CHANNELS]> {
     #[must_use]
     pub fn width(&self) -> NonZeroU32 {
@@ -214,6 +328,44 @@ impl<T: BorrowableImageChannel, const CHANNELS: usize> ImageChannels<[T; CHANNEL
         self.0[0].borrow().len_flat()
     }
 
+    /// Returns the pixel of each channel at (`x`, `y`), or `None` if it is out of bounds
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<[&T::Pixel; CHANNELS]> {
+        if x >= self.width().get() || y >= self.height().get() {
+            return None;
+        }
+        // All channels have the same dimensions (validated at construction)
+        Some(self.0.each_ref().map(|channel| {
+            channel
+                .borrow()
+                .get_pixel(x, y)
+                .expect("All channels have the same dimensions")
+        }))
+    }
+
+    /// Iterates over the rows of all channels from top to bottom. Works for strided channels too
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = [&[T::Pixel]; CHANNELS]> + '_ {
+        let mut rows = self.0.each_ref().map(|channel| channel.borrow().rows());
+        (0..self.height().get()).map(move |_| {
+            rows.each_mut()
+                .map(|rows| rows.next().expect("All channels have the same height"))
+        })
+    }
+
+    /// Iterates over all pixels row by row, yielding (`x`, `y`, pixel of each channel)
+    pub fn enumerate_pixels(&self) -> impl Iterator<Item = (u32, u32, [&T::Pixel; CHANNELS])> + '_ {
+        (0..).zip(self.rows()).flat_map(|(y, row)| {
+            (0..)
+                .zip(0..row[0].len())
+                .map(move |(x, i)| (x, y, row.map(|row| &row[i])))
+        })
+    }
+
+    /// # Panics
+    /// Panics if any channel is not contiguous. See [`ImageChannel::rows`] for strided channels
     #[must_use]
     pub fn buffers(&self) -> [&[T::Pixel]; CHANNELS] {
         let mut uninit = [&[] as &[T::Pixel]; CHANNELS];
@@ -227,25 +379,73 @@ impl<T: BorrowableImageChannel, const CHANNELS: usize> ImageChannels<[T; CHANNEL
     }
 }
 
+impl<T: BorrowableImageChannel, const CHANNELS: usize> ImageChannels<[T; CHANNELS]>
+where
+    <T::Pixel as RuntimePixelType>::Primitive: Into<f32> + Copy,
+{
+    /// Lossless conversion of all channels into `f32` (e.g. `Image<half::f16, 3>` -> `Image<f32, 3>`)
+    #[must_use]
+    pub fn to_f32(&self) -> Image<<T::Pixel as RuntimePixelType>::WithPrimitive<f32>, CHANNELS> {
+        ImageChannels(
+            self.0.each_ref().map(|channel| channel.borrow().to_f32()),
+            self.1.clone(),
+        )
+    }
+}
+
 impl<T> Image<T, 1>
 where
     T: PixelType,
 {
-    pub fn new_arc(input: Arc<[T]>, width: NonZeroU32, height: NonZeroU32) -> Self
-    where
-        T::Primitive: Clone,
-    {
+    /// # Panics
+    /// Panics if the buffer size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_arc(input: Arc<[T]>, width: NonZeroU32, height: NonZeroU32) -> Self {
         let channel = ImageChannel::new_arc(input, width, height);
-        Self([channel])
+        Self([channel], Metadata::new())
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_arc(
+        input: Arc<[T]>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Arc<[T]>>> {
+        ImageChannel::try_new_arc(input, width, height)
+            .map(|channel| Self([channel], Metadata::new()))
+    }
+
+    /// # Panics
+    /// Panics if the buffer size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_box(input: Box<[T]>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        let channel = ImageChannel::new_box(input, width, height);
+        Self([channel], Metadata::new())
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_box(
+        input: Box<[T]>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Box<[T]>>> {
+        ImageChannel::try_new_box(input, width, height)
+            .map(|channel| Self([channel], Metadata::new()))
     }
 }
 
 impl<T: BorrowableImageChannel> ImageChannels<[T; 1]> {
+    /// # Panics
+    /// Panics if the channel is not contiguous
     #[must_use]
     pub fn buffer(&self) -> &[T::Pixel] {
         self.0[0].borrow().buffer()
     }
 
+    /// # Panics
+    /// Panics if the channel is not contiguous
     #[must_use]
     pub fn buffer_flat(&self) -> &[<T::Pixel as RuntimePixelType>::Primitive] {
         self.0[0].borrow().buffer_flat()
@@ -253,6 +453,8 @@ impl<T: BorrowableImageChannel> ImageChannels<[T; 1]> {
 }
 
 impl<const PIXEL_ELEMENTS: usize, T: PixelTypePrimitive> Image<[T; PIXEL_ELEMENTS], 1> {
+    /// # Panics
+    /// Panics if any channel of `i` is not contiguous
     #[must_use]
     pub fn from_planar_image<const CHANNELS: usize>(i: &Image<T, CHANNELS>) -> Self
     where
@@ -286,7 +488,7 @@ impl<const PIXEL_ELEMENTS: usize, T: PixelTypePrimitive> Image<[T; PIXEL_ELEMENT
                     std::mem::MaybeUninit::<[ImageChannel<[T; PIXEL_ELEMENTS]>; 1]>::uninit();
                 unsafe {
                     std::ptr::write(arr.as_mut_ptr().cast(), channel);
-                    Self(arr.assume_init())
+                    Self(arr.assume_init(), Metadata::new())
                 }
             };
         }
@@ -333,7 +535,7 @@ impl<const PIXEL_ELEMENTS: usize, T: PixelTypePrimitive> Image<[T; PIXEL_ELEMENT
         let data = std::sync::Arc::from(data_vec_init);
 
         let image = ImageChannel::new_arc(data, width, height);
-        Self([image])
+        Self([image], Metadata::new())
     }
 }
 
@@ -365,7 +567,7 @@ impl<T: BorrowableImageChannel, const CHANNELS: usize> TryFrom<[T; CHANNELS]>
                 reason: IncompatibleImageErrorReason::MixedImageSizes { a, b },
             })
         } else {
-            Ok(Self(channels))
+            Ok(Self(channels, Metadata::new()))
         }
     }
 }
@@ -464,6 +666,27 @@ mod tests {
         }
     }
 
+    #[test]
+    fn miri_planar_to_vec_reuses_pointer() {
+        let raw = vec![[0u8, 1], [2, 3], [4, 5], [6, 7]];
+        let pointer = raw.as_ptr();
+        let size = NonZeroU32::new(2).unwrap();
+        let mut image = crate::Image::<[u8; 2], 2>::new_vec(raw, size, NonZeroU32::MIN);
+        image.make_mut()[1][0] = [8, 9];
+        let to_vec = image.clone().into_vec();
+        assert_ne!(to_vec.as_ptr(), pointer, "Should copy shared channels");
+
+        let to_vec = image.into_vec();
+        assert_eq!(to_vec, [[0, 1], [2, 3], [8, 9], [6, 7]]);
+        if !cfg!(miri) {
+            assert_eq!(
+                to_vec.as_ptr(),
+                pointer,
+                "Should reuse the buffer of unique channels"
+            );
+        }
+    }
+
     #[test]
     fn miri_clone_from_box() {
         let raw = vec![0u8, 64u8, 128u8, 192u8];
@@ -494,4 +717,111 @@ mod tests {
 
         assert_eq!(image, image_from_flat);
     }
+
+    #[test]
+    fn u16_to_f32() {
+        let image = Image::<u16, 2>::new_vec(vec![0, u16::MAX], NonZeroU32::MIN, NonZeroU32::MIN);
+        assert_eq!(image.to_f32().buffers(), [[0f32], [65535f32]]);
+    }
+
+    #[test]
+    #[cfg(feature = "half")]
+    fn half_to_f32() {
+        use half::{bf16, f16};
+
+        let image = Image::<[f16; 3], 1>::new_vec(
+            vec![[f16::from_f32(0.5), f16::ONE, f16::NEG_INFINITY]],
+            NonZeroU32::MIN,
+            NonZeroU32::MIN,
+        );
+        let widened: Image<[f32; 3], 1> = image.to_f32();
+        assert_eq!(widened.buffer(), &[[0.5, 1., f32::NEG_INFINITY]]);
+
+        let image =
+            Image::<bf16, 1>::new_vec(vec![bf16::from_f32(-2.)], NonZeroU32::MIN, NonZeroU32::MIN);
+        assert_eq!(image.to_f32().buffer(), &[-2f32]);
+    }
+
+    #[test]
+    fn pixel_accessors() {
+        let two = NonZeroU32::new(2).unwrap();
+        let mut image = Image::<u8, 2>::new_vec((0..8).collect(), two, two);
+        assert_eq!(image.get_pixel(1, 0), Some([&1, &5]));
+        assert_eq!(image.get_pixel(2, 0), None);
+        assert_eq!(image.get_pixel(0, 2), None);
+
+        let [a, b] = image.get_pixel_mut(0, 1).unwrap();
+        *a = 20;
+        *b = 60;
+        assert!(image.get_pixel_mut(0, 2).is_none());
+        assert_eq!(image.buffers(), [[0, 1, 20, 3], [4, 5, 60, 7]]);
+    }
+
+    #[test]
+    fn rows_of_all_channels() {
+        let two = NonZeroU32::new(2).unwrap();
+        let mut image = Image::<u8, 2>::new_vec((0..8).collect(), two, two);
+        assert_eq!(
+            image.rows().collect::<Vec<_>>(),
+            [[[0u8, 1].as_slice(), &[4, 5]], [&[2, 3], &[6, 7]]]
+        );
+
+        for [a, b] in image.rows_mut() {
+            a[0] = b[1];
+        }
+        assert_eq!(image.buffers(), [[5, 1, 7, 3], [4, 5, 6, 7]]);
+    }
+
+    #[test]
+    fn enumerate_pixels() {
+        let image = Image::<[u8; 2], 1>::new_vec(
+            vec![[0, 1], [2, 3], [4, 5]],
+            NonZeroU32::new(3).unwrap(),
+            NonZeroU32::MIN,
+        );
+        assert_eq!(
+            image.enumerate_pixels().collect::<Vec<_>>(),
+            [(0, 0, [&[0u8, 1]]), (1, 0, [&[2, 3]]), (2, 0, [&[4, 5]])]
+        );
+    }
+
+    #[test]
+    fn try_new_vec_returns_buffer_on_size_mismatch() {
+        let two = NonZeroU32::new(2).unwrap();
+        let err = Image::<u8, 3>::try_new_vec(vec![0; 11], two, two).unwrap_err();
+        assert_eq!(
+            (err.expected, err.actual, err.buffer.len()),
+            (Some(12), 11, 11)
+        );
+
+        let err = Image::<[u16; 3], 2>::try_new_vec_flat(vec![0; 12], two, two).unwrap_err();
+        assert_eq!((err.expected, err.actual), (Some(24), 12));
+        assert!(Image::<[u16; 3], 2>::try_new_vec_flat(vec![0; 24], two, two).is_ok());
+
+        let err = Image::<u8, 1>::try_new_arc(vec![0; 3].into(), two, two).unwrap_err();
+        assert_eq!(err.buffer.len(), 3);
+    }
+
+    #[test]
+    fn try_new_vec_reports_overflowing_dimensions() {
+        let max = NonZeroU32::MAX;
+        let err = Image::<[u16; 3], 2>::try_new_vec_flat(vec![0; 6], max, max).unwrap_err();
+        assert_eq!((err.expected, err.actual), (None, 6));
+        assert_eq!(
+            err.to_string(),
+            "Incompatible Buffer-Size: expected more than usize::MAX elements, got 6"
+        );
+
+        let err = Image::<[u8; 4], 3>::try_new_vec(vec![[0; 4]; 3], max, max).unwrap_err();
+        assert_eq!(err.expected, None);
+    }
+
+    #[test]
+    fn try_from_flat_interleaved_with_wrong_size() {
+        let data = [0u8; 5];
+        let err =
+            Image::<u8, 2>::try_from_flat_interleaved(&data, (NonZeroU32::MIN, NonZeroU32::MIN))
+                .unwrap_err();
+        assert_eq!((err.expected, err.actual), (Some(2), 5));
+    }
 }
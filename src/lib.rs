@@ -3,22 +3,62 @@
 
 use std::num::NonZeroU8;
 
+mod aligned;
 mod arc;
+mod array_interface;
+mod boxed;
+#[cfg(feature = "bytes")]
+mod bytes;
 mod channel;
+#[cfg(feature = "color")]
+pub mod color;
+mod depth;
+#[cfg(feature = "dlpack")]
+pub mod dlpack;
 mod dynamic;
 mod external;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod image;
+mod metadata;
+#[cfg(feature = "memmap2")]
+mod mmap;
+#[cfg(feature = "npy")]
+mod npy;
 mod pixel;
 mod pixel_elements;
+mod pool;
+mod semantics;
 mod shared_vec;
+mod tuple;
 mod vec;
+mod view;
+mod yuv;
 
-pub use channel::{BorrowableImageChannel, ImageChannel, ImageChannelVTable, UnsafeImageChannel};
-pub use dynamic::{DynamicImage, DynamicImageChannel, IncompatibleImageError};
-#[cfg(feature = "image_0_25")]
+pub use array_interface::{ArrayExport, ArrayInterface};
+#[cfg(feature = "bytes")]
+pub use bytes::BytesChannelError;
+pub use channel::{
+    BorrowableImageChannel, IMAGE_CHANNEL_ABI_VERSION, ImageChannel, ImageChannelAbi,
+    ImageChannelVTable, IncompatibleAbiError, IncompatibleBufferSizeError, UnsafeImageChannel,
+};
+pub use depth::DepthScaling;
+pub use dynamic::{
+    DynamicImage, DynamicImageChannel, IncompatibleImageError, IncompatibleImageErrorReason,
+};
+#[cfg(any(feature = "image_0_25", feature = "nalgebra", feature = "ndarray"))]
 pub use external::*;
 pub use image::{Image, ImageChannels, ImageMut, ImageRef};
+pub use metadata::Metadata;
+#[cfg(feature = "memmap2")]
+pub use mmap::MmapChannelError;
+#[cfg(feature = "npy")]
+pub use npy::{NpyError, NpyLayout};
 pub use pixel::{DynamicSize, PixelType, PixelTypePrimitive};
+pub use pool::ChannelPool;
+pub use semantics::{ChannelLayout, ChannelSemantics, TransferFunction};
+pub use view::{ImageChannelView, ImageChannelViewMut};
+pub use yuv::{I420Image, Nv12Image, YuyvImage};
 
 #[deprecated(
     since = "0.3.0",
@@ -53,17 +93,89 @@ const fn unwrap_usize_to_nonzero_u8(value: usize) -> NonZeroU8 {
 }
 
 mod seal {
-    use crate::{ImageChannel, PixelType};
+    use crate::{ImageChannel, ImageChannelView, ImageChannelViewMut, PixelType};
 
     /// Allows to forbid external implementations to add new primitives
     /// This crate heavily relies on casting between primitive pointers
-    pub trait SealedPrimitive {}
-    impl SealedPrimitive for u8 {}
-    impl SealedPrimitive for u16 {}
-    impl SealedPrimitive for f32 {}
+    /// Numeric helpers are hidden here, so they don't become part of the public API
+    pub trait SealedPrimitive: Copy {
+        /// Maps the nominal range (`0..=MAX` for integers, `0.0..=1.0` for floats) to `0.0..=1.0`
+        fn to_unit_f64(self) -> f64;
+        /// Inverse of [`Self::to_unit_f64`], which rounds and saturates for integers
+        fn from_unit_f64(value: f64) -> Self;
+        fn to_f64(self) -> f64;
+        /// Like `value as Self`, which truncates and saturates for integers
+        fn from_f64(value: f64) -> Self;
+        /// Rounds to the nearest integer and saturates for integers
+        fn from_f64_rounded(value: f64) -> Self;
+        /// Type kind of `NumPy`'s `typestr` (e.g. `u` for unsigned integers)
+        const ARRAY_KIND: char;
+    }
+
+    macro_rules! impl_sealed_integer {
+        ($($ty:ty),*) => {$(
+            impl SealedPrimitive for $ty {
+                const ARRAY_KIND: char = if <$ty>::MIN == 0 { 'u' } else { 'i' };
+                fn to_unit_f64(self) -> f64 {
+                    f64::from(self) / f64::from(<$ty>::MAX)
+                }
+                fn from_unit_f64(value: f64) -> Self {
+                    Self::from_f64_rounded(value * f64::from(<$ty>::MAX))
+                }
+                fn to_f64(self) -> f64 {
+                    f64::from(self)
+                }
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                fn from_f64(value: f64) -> Self {
+                    value as $ty
+                }
+                fn from_f64_rounded(value: f64) -> Self {
+                    Self::from_f64(value.round())
+                }
+            }
+        )*};
+    }
+    impl_sealed_integer!(u8, i8, u16, i16, u32, i32);
+
+    /// Floats have the nominal range `0.0..=1.0` and are never rounded
+    macro_rules! impl_sealed_float {
+        ($ty:ty, $kind:literal, $to_f64:expr, $from_f64:expr) => {
+            impl SealedPrimitive for $ty {
+                const ARRAY_KIND: char = $kind;
+                fn to_unit_f64(self) -> f64 {
+                    $to_f64(self)
+                }
+                fn from_unit_f64(value: f64) -> Self {
+                    $from_f64(value)
+                }
+                fn to_f64(self) -> f64 {
+                    $to_f64(self)
+                }
+                fn from_f64(value: f64) -> Self {
+                    $from_f64(value)
+                }
+                fn from_f64_rounded(value: f64) -> Self {
+                    $from_f64(value)
+                }
+            }
+        };
+    }
+    impl_sealed_float!(f32, 'f', f64::from, |x| {
+        #[allow(clippy::cast_possible_truncation)]
+        let x = x as f32;
+        x
+    });
+    impl_sealed_float!(f64, 'f', std::convert::identity, std::convert::identity);
+    #[cfg(feature = "half")]
+    impl_sealed_float!(half::f16, 'f', half::f16::to_f64, half::f16::from_f64);
+    // NumPy has no bfloat16, so it is exported as raw 2 byte values
+    #[cfg(feature = "half")]
+    impl_sealed_float!(half::bf16, 'V', half::bf16::to_f64, half::bf16::from_f64);
 
     pub trait SealedImageChannel {}
     impl<T: PixelType> SealedImageChannel for ImageChannel<T> {}
     impl<T: PixelType> SealedImageChannel for &ImageChannel<T> {}
     impl<T: PixelType> SealedImageChannel for &mut ImageChannel<T> {}
+    impl<T: PixelType> SealedImageChannel for ImageChannelView<'_, T> {}
+    impl<T: PixelType> SealedImageChannel for ImageChannelViewMut<'_, T> {}
 }
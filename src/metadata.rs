@@ -0,0 +1,197 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+/// Typed values attached to an image (e.g. timestamp, exposure or camera id), where each type is stored at most once
+///
+/// Values are shared between clones and only copied by [`Metadata::get_mut`], if another clone still references them.
+/// The metadata is moved along by `From<Image>` and `TryFrom<DynamicImage>` and is ignored when images are compared.
+/// Borrowed images (`ImageRef`, `ImageMut`) get a read-only clone, as there is no `metadata_mut` for them.
+#[derive(Clone, Default)]
+// Boxed, so images without metadata stay small
+#[allow(clippy::box_collection)]
+pub struct Metadata(Option<Box<HashMap<TypeId, Arc<dyn MetadataValue>>>>);
+
+trait MetadataValue: Any + Send + Sync {
+    fn clone_arc(&self) -> Arc<dyn MetadataValue>;
+}
+
+impl<T: Clone + Send + Sync + 'static> MetadataValue for T {
+    fn clone_arc(&self) -> Arc<dyn MetadataValue> {
+        Arc::new(self.clone())
+    }
+}
+
+impl Metadata {
+    /// Doesn't allocate until the first value is inserted
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(None)
+    }
+
+    /// Returns the previous value of the same type
+    pub fn insert<T: Clone + Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.0
+            .get_or_insert_default()
+            .insert(TypeId::of::<T>(), Arc::new(value))
+            .map(unwrap_or_clone)
+    }
+
+    #[must_use]
+    pub fn get<T: 'static>(&self) -> Option<&T> {
+        let value: &dyn Any = self.0.as_ref()?.get(&TypeId::of::<T>())?.as_ref();
+        value.downcast_ref()
+    }
+
+    /// Copies the value, if it is shared with a clone of this metadata
+    pub fn get_mut<T: 'static>(&mut self) -> Option<&mut T> {
+        let value = self.0.as_mut()?.get_mut(&TypeId::of::<T>())?;
+        if Arc::get_mut(value).is_none() {
+            *value = (**value).clone_arc();
+        }
+        let value: &mut dyn Any = Arc::get_mut(value)?;
+        value.downcast_mut()
+    }
+
+    pub fn remove<T: Clone + Send + Sync + 'static>(&mut self) -> Option<T> {
+        self.0
+            .as_mut()?
+            .remove(&TypeId::of::<T>())
+            .map(unwrap_or_clone)
+    }
+
+    #[must_use]
+    pub fn contains<T: 'static>(&self) -> bool {
+        self.0
+            .as_ref()
+            .is_some_and(|map| map.contains_key(&TypeId::of::<T>()))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |map| map.len())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.0 = None;
+    }
+
+    /// Inserts all values of `other`, replacing values of the same type
+    pub fn extend(&mut self, other: Metadata) {
+        if let Some(other) = other.0 {
+            match &mut self.0 {
+                Some(map) => map.extend(*other),
+                None => self.0 = Some(other),
+            }
+        }
+    }
+}
+
+fn unwrap_or_clone<T: Clone + Send + Sync + 'static>(value: Arc<dyn MetadataValue>) -> T {
+    let value: Arc<dyn Any + Send + Sync> = value;
+    Arc::unwrap_or_clone(
+        value
+            .downcast()
+            .unwrap_or_else(|_| unreachable!("Values are stored by their TypeId")),
+    )
+}
+
+impl Debug for Metadata {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metadata")
+            .field("len", &self.len())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::Metadata;
+    use crate::{DynamicImage, Image, ImageRef};
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Exposure(f32);
+    #[derive(Debug, Clone, PartialEq)]
+    struct CameraId(&'static str);
+
+    #[test]
+    fn typed_values() {
+        let mut metadata = Metadata::new();
+        assert!(metadata.is_empty());
+        assert_eq!(metadata.insert(Exposure(1.0)), None);
+        assert_eq!(metadata.insert(Exposure(2.0)), Some(Exposure(1.0)));
+        metadata.insert(CameraId("left"));
+        assert_eq!(metadata.len(), 2);
+        assert_eq!(metadata.get::<Exposure>(), Some(&Exposure(2.0)));
+        assert!(metadata.contains::<CameraId>());
+        assert_eq!(metadata.get::<u64>(), None);
+
+        assert_eq!(metadata.remove::<CameraId>(), Some(CameraId("left")));
+        assert!(!metadata.contains::<CameraId>());
+        metadata.clear();
+        assert!(metadata.is_empty());
+    }
+
+    #[test]
+    fn get_mut_copies_shared_values() {
+        let mut metadata = Metadata::new();
+        metadata.insert(Exposure(1.0));
+        let clone = metadata.clone();
+        metadata.get_mut::<Exposure>().unwrap().0 = 2.0;
+        assert_eq!(metadata.get::<Exposure>(), Some(&Exposure(2.0)));
+        assert_eq!(clone.get::<Exposure>(), Some(&Exposure(1.0)));
+    }
+
+    #[test]
+    fn extend_replaces_existing_values() {
+        let mut a = Metadata::new();
+        a.insert(Exposure(1.0));
+        let mut b = Metadata::new();
+        b.insert(Exposure(2.0));
+        b.insert(CameraId("right"));
+        a.extend(b);
+        assert_eq!(a.get::<Exposure>(), Some(&Exposure(2.0)));
+        assert_eq!(a.get::<CameraId>(), Some(&CameraId("right")));
+    }
+
+    #[test]
+    fn survives_dynamic_roundtrip() {
+        let mut image = Image::<u8, 2>::new_vec(vec![1, 2], NonZeroU32::MIN, NonZeroU32::MIN);
+        let ptr = image.buffers()[0].as_ptr();
+        image.metadata_mut().insert(CameraId("left"));
+
+        let mut dynamic = DynamicImage::from(image);
+        assert_eq!(dynamic.metadata().get(), Some(&CameraId("left")));
+        dynamic.metadata_mut().insert(Exposure(0.5));
+
+        let borrowed: ImageRef<u8, 2> = (&dynamic).try_into().unwrap();
+        assert_eq!(borrowed.metadata().get(), Some(&Exposure(0.5)));
+
+        let image: Image<u8, 2> = dynamic.try_into().unwrap();
+        assert_eq!(image.buffers()[0].as_ptr(), ptr);
+        assert_eq!(image.metadata().get(), Some(&CameraId("left")));
+        assert_eq!(image.metadata().get(), Some(&Exposure(0.5)));
+
+        let error = Image::<u16, 2>::try_from(DynamicImage::from(image)).unwrap_err();
+        assert_eq!(error.image.metadata().len(), 2);
+    }
+
+    #[test]
+    fn ignored_by_eq() {
+        let image = Image::<u8, 1>::new_vec(vec![1], NonZeroU32::MIN, NonZeroU32::MIN);
+        let mut with_metadata = image.clone();
+        with_metadata.metadata_mut().insert(Exposure(1.0));
+        assert_eq!(image, with_metadata);
+        assert_eq!(DynamicImage::from(image), DynamicImage::from(with_metadata));
+    }
+}
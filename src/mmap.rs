@@ -0,0 +1,234 @@
+use std::{
+    num::{NonZeroU8, NonZeroU32},
+    sync::Arc,
+};
+
+use memmap2::Mmap;
+
+use crate::{
+    Image, ImageChannel, Metadata, PixelType, PixelTypePrimitive,
+    channel::{UnsafeImageChannel, calc_pixel_len_flat, checked_calc_pixel_len_flat},
+    pixel_elements::PixelSize,
+};
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum MmapChannelError {
+    #[error(
+        "The mapping is too small: expected {} bytes after the offset, got {actual}",
+        .expected.map_or_else(|| "more than usize::MAX".to_owned(), |x| x.to_string())
+    )]
+    OutOfBounds {
+        /// `None`, if the dimensions overflow `usize`
+        expected: Option<usize>,
+        actual: usize,
+    },
+    #[error("Offset {offset} is not aligned to {align} bytes")]
+    Misaligned { offset: usize, align: usize },
+}
+
+impl<T: PixelTypePrimitive> UnsafeImageChannel<T> {
+    /// Creates a read-only channel over `mmap[offset..]` without copying. Pixels are interpreted in native endianness.
+    ///
+    /// # Errors
+    /// Fails if `offset` is not aligned for `T` or the mapping is too small
+    pub fn try_new_mmap(
+        mmap: Arc<Mmap>,
+        offset: usize,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Result<Self, MmapChannelError> {
+        let align = std::mem::align_of::<T>();
+        let ptr = mmap.as_ptr().wrapping_add(offset);
+        if !ptr.cast::<T>().is_aligned() {
+            return Err(MmapChannelError::Misaligned { offset, align });
+        }
+        check_bounds(
+            &mmap,
+            offset,
+            checked_calc_pixel_len_flat(width, height, pixel_elements)
+                .and_then(|len| len.checked_mul(size_of::<T>())),
+        )?;
+
+        Ok(unsafe {
+            Self::new_arc_owner(
+                ptr.cast(),
+                width,
+                height,
+                calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
+                pixel_elements,
+                mmap,
+            )
+        })
+    }
+}
+
+fn check_bounds(
+    mmap: &Mmap,
+    offset: usize,
+    expected: Option<usize>,
+) -> Result<(), MmapChannelError> {
+    let actual = mmap.len().saturating_sub(offset);
+    match expected {
+        Some(expected) if expected <= actual => Ok(()),
+        _ => Err(MmapChannelError::OutOfBounds { expected, actual }),
+    }
+}
+
+impl<TP: PixelType> ImageChannel<TP> {
+    /// Creates a read-only channel over `mmap[offset..]` without copying. Pixels are interpreted in native endianness.
+    ///
+    /// [`ImageChannel::make_mut`] copies the pixels into a Vec.
+    ///
+    /// # Errors
+    /// Fails if `offset` is not aligned for `TP::Primitive` or the mapping is too small
+    pub fn try_new_mmap(
+        mmap: Arc<Mmap>,
+        offset: usize,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, MmapChannelError> {
+        UnsafeImageChannel::try_new_mmap(
+            mmap,
+            offset,
+            width,
+            height,
+            TP::PixelSize::default().get(),
+        )
+        .map(Self)
+    }
+}
+
+impl<T: PixelType, const CHANNELS: usize> Image<T, CHANNELS> {
+    /// Creates a read-only image over `mmap[offset..]` without copying, where all channels are stored consecutively
+    ///
+    /// # Errors
+    /// Fails if `offset` is not aligned for `T::Primitive` or the mapping is too small
+    pub fn try_new_mmap(
+        mmap: Arc<Mmap>,
+        offset: usize,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, MmapChannelError> {
+        let channel_bytes = checked_calc_pixel_len_flat(width, height, T::ELEMENTS)
+            .and_then(|len| len.checked_mul(size_of::<T::Primitive>()));
+        // Afterwards the offsets of all channels are within the mapping
+        check_bounds(
+            &mmap,
+            offset,
+            channel_bytes.and_then(|bytes| bytes.checked_mul(CHANNELS)),
+        )?;
+        let channel_bytes = channel_bytes.unwrap_or_default();
+        // Moves `mmap` into the last channel
+        let channels = std::iter::repeat_n(mmap, CHANNELS)
+            .enumerate()
+            .map(|(i, mmap)| {
+                ImageChannel::try_new_mmap(mmap, offset + i * channel_bytes, width, height)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let Ok(channels) = channels.try_into() else {
+            unreachable!("Exactly CHANNELS channels are created")
+        };
+        Ok(Self(channels, Metadata::new()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{io::Write, num::NonZeroU32, sync::Arc};
+
+    use memmap2::Mmap;
+
+    use super::MmapChannelError;
+    use crate::{Image, ImageChannel};
+
+    fn map(name: &str, content: &[u8]) -> Arc<Mmap> {
+        let path = std::env::temp_dir().join(format!("imbuf-{}-{name}", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(content).unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        let mmap = unsafe { Mmap::map(&file) }.unwrap();
+        std::fs::remove_file(path).unwrap();
+        Arc::new(mmap)
+    }
+
+    #[test]
+    fn channel_shares_mapping() {
+        let mmap = map("shares", &[0, 1, 2, 3, 4]);
+        let two = NonZeroU32::new(2).unwrap();
+        let channel = ImageChannel::<u8>::try_new_mmap(mmap.clone(), 1, two, two).unwrap();
+        assert_eq!(channel.buffer(), &[1, 2, 3, 4]);
+        assert_eq!(channel.buffer().as_ptr(), mmap[1..].as_ptr());
+
+        let clone = channel.clone();
+        assert_eq!(clone.buffer().as_ptr(), mmap[1..].as_ptr());
+        drop(channel);
+        assert_eq!(Arc::strong_count(&mmap), 2);
+        drop(clone);
+        assert_eq!(Arc::strong_count(&mmap), 1);
+    }
+
+    #[test]
+    fn make_mut_copies_into_vec() {
+        let mmap = map("make_mut", &[0, 1]);
+        let mut channel =
+            ImageChannel::<u8>::try_new_mmap(mmap.clone(), 0, NonZeroU32::MIN, NonZeroU32::MIN)
+                .unwrap();
+        channel.make_mut()[0] = 42;
+        assert_eq!(channel.buffer(), &[42]);
+        assert_eq!(mmap[0], 0);
+        assert_eq!(Arc::strong_count(&mmap), 1);
+    }
+
+    #[test]
+    fn image_with_consecutive_channels() {
+        let content = [1u16, 2, 3, 4].map(u16::to_ne_bytes).concat();
+        let mmap = map("image", &content);
+        let image =
+            Image::<u16, 2>::try_new_mmap(mmap, 0, NonZeroU32::new(2).unwrap(), NonZeroU32::MIN)
+                .unwrap();
+        assert_eq!(image.buffers(), [[1, 2], [3, 4]]);
+    }
+
+    #[test]
+    fn reject_invalid_offsets() {
+        let mmap = map("invalid", &[0; 4]);
+        let two = NonZeroU32::new(2).unwrap();
+        assert!(matches!(
+            ImageChannel::<u16>::try_new_mmap(mmap.clone(), 1, NonZeroU32::MIN, NonZeroU32::MIN),
+            Err(MmapChannelError::Misaligned {
+                offset: 1,
+                align: 2
+            })
+        ));
+        assert!(matches!(
+            ImageChannel::<u16>::try_new_mmap(mmap.clone(), 2, two, NonZeroU32::MIN),
+            Err(MmapChannelError::OutOfBounds {
+                expected: Some(4),
+                actual: 2
+            })
+        ));
+        assert!(matches!(
+            ImageChannel::<u8>::try_new_mmap(mmap.clone(), 6, NonZeroU32::MIN, NonZeroU32::MIN),
+            Err(MmapChannelError::OutOfBounds {
+                expected: Some(1),
+                actual: 0
+            })
+        ));
+        assert!(matches!(
+            ImageChannel::<u16>::try_new_mmap(mmap.clone(), 0, NonZeroU32::MAX, NonZeroU32::MAX),
+            Err(MmapChannelError::OutOfBounds {
+                expected: None,
+                actual: 4
+            })
+        ));
+        assert!(matches!(
+            Image::<u8, 2>::try_new_mmap(mmap, usize::MAX, two, NonZeroU32::MIN),
+            Err(MmapChannelError::OutOfBounds {
+                expected: Some(4),
+                actual: 0
+            })
+        ));
+    }
+}
@@ -0,0 +1,477 @@
+//! Reading and writing of `NumPy`'s `.npy` files (format version 1.0 to 3.0)
+use std::{
+    io::{Read, Write},
+    num::{NonZeroU8, NonZeroU32},
+};
+
+use crate::{
+    DynamicImage, DynamicImageChannel, DynamicSize, ImageChannel, PixelTypePrimitive,
+    array_interface::typestr, channel::UnsafeImageChannel, dynamic::match_dynamic_channel,
+    pixel::RuntimePixelType, shared_vec::create_shared_planes,
+};
+
+const MAGIC: &[u8] = b"\x93NUMPY";
+/// Size of the chunks, in which data is read
+const READ_CHUNK_BYTES: usize = 1 << 20;
+
+/// Order of the axes in `.npy` files
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NpyLayout {
+    /// `height × width × elements`, where the elements of all channels are stored next to each other
+    #[default]
+    Interleaved,
+    /// `elements × height × width`, where every element of every channel is stored as plane
+    Planar,
+}
+
+#[non_exhaustive]
+#[derive(Debug, thiserror::Error)]
+pub enum NpyError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Invalid npy header: {reason}")]
+    InvalidHeader { reason: &'static str },
+    #[error("Unsupported dtype {descr}")]
+    UnsupportedDtype { descr: String },
+    /// Files must have 2 or 3 dimensions, where height and width are within `1..=u32::MAX` and interleaved elements within `1..=255`
+    #[error("Unsupported shape {shape:?}")]
+    UnsupportedShape { shape: Vec<usize> },
+    #[error("Fortran order is not supported")]
+    FortranOrder,
+    /// All channels of an image must have the same primitive to be written into one file
+    #[error("Channels have different primitives")]
+    MixedPrimitives,
+}
+
+impl<TP: RuntimePixelType> ImageChannel<TP> {
+    /// Writes the channel as `height × width × elements` or `elements × height × width` array
+    ///
+    /// # Errors
+    /// Fails if writing fails or the primitive has no `NumPy` dtype (`bf16`)
+    pub fn write_npy(&self, writer: impl Write, layout: NpyLayout) -> Result<(), NpyError> {
+        write_channels::<TP::Primitive>(writer, &[&self.0], layout)
+    }
+}
+
+impl DynamicImage {
+    /// Writes all channels into one array (see [`NpyLayout`])
+    ///
+    /// # Errors
+    /// Fails if writing fails, the channels have different primitives or the primitive has no `NumPy` dtype (`bf16`)
+    pub fn write_npy(&self, writer: impl Write, layout: NpyLayout) -> Result<(), NpyError> {
+        fn write<T: PixelTypePrimitive>(
+            image: &DynamicImage,
+            _first: &ImageChannel<DynamicSize<T>>,
+            writer: impl Write,
+            layout: NpyLayout,
+        ) -> Result<(), NpyError> {
+            let channels = image
+                .iter()
+                .map(|channel| T::try_from_dynamic_image_ref(channel).map(|channel| &channel.0))
+                .collect::<Option<Vec<_>>>()
+                .ok_or(NpyError::MixedPrimitives)?;
+            write_channels::<T>(writer, &channels, layout)
+        }
+        match_dynamic_channel!(self.first(), first => write(self, first, writer, layout))
+    }
+
+    /// Reads an array of `height × width` or a 3 dimensional array with the given `layout`.
+    /// Interleaved files become one channel. Planar files become one channel per plane, which share the read `Vec`.
+    ///
+    /// # Errors
+    /// Fails if reading fails or the file has an unsupported dtype, shape or order
+    pub fn read_npy(mut reader: impl Read, layout: NpyLayout) -> Result<Self, NpyError> {
+        let header = read_header(&mut reader)?;
+        let swap = match header.descr.split_at_checked(1) {
+            Some(("|" | "=", _)) => false,
+            Some(("<", _)) => cfg!(target_endian = "big"),
+            Some((">", _)) => cfg!(target_endian = "little"),
+            _ => {
+                return Err(NpyError::UnsupportedDtype {
+                    descr: header.descr,
+                });
+            }
+        };
+        let read = match &header.descr[1..] {
+            "u1" => read_channels::<u8>,
+            "i1" => read_channels::<i8>,
+            "u2" => read_channels::<u16>,
+            "i2" => read_channels::<i16>,
+            "u4" => read_channels::<u32>,
+            "i4" => read_channels::<i32>,
+            "f4" => read_channels::<f32>,
+            "f8" => read_channels::<f64>,
+            #[cfg(feature = "half")]
+            "f2" => read_channels::<half::f16>,
+            _ => {
+                return Err(NpyError::UnsupportedDtype {
+                    descr: header.descr,
+                });
+            }
+        };
+        read(&mut reader, &header.shape, swap, layout)
+    }
+}
+
+fn write_channels<T: PixelTypePrimitive>(
+    mut writer: impl Write,
+    channels: &[&UnsafeImageChannel<T>],
+    layout: NpyLayout,
+) -> Result<(), NpyError> {
+    let descr = typestr::<T>();
+    if descr.contains('V') {
+        return Err(NpyError::UnsupportedDtype { descr });
+    }
+    let first = channels[0];
+    let (width, height) = (first.width, first.height);
+    if let Some(channel) = channels
+        .iter()
+        .find(|channel| (channel.width, channel.height) != (width, height))
+    {
+        return Err(NpyError::UnsupportedShape {
+            shape: vec![channel.height.get() as usize, channel.width.get() as usize],
+        });
+    }
+    let elements = channels
+        .iter()
+        .map(|channel| usize::from(channel.pixel_elements.get()))
+        .sum::<usize>();
+    let (height, width) = (height.get() as usize, width.get() as usize);
+    let shape = match layout {
+        NpyLayout::Interleaved => [height, width, elements],
+        NpyLayout::Planar => [elements, height, width],
+    };
+    write_header(&mut writer, &descr, shape)?;
+
+    match layout {
+        NpyLayout::Interleaved if channels.len() == 1 => {
+            for row in first.rows_flat() {
+                writer.write_all(as_bytes(row))?;
+            }
+        }
+        NpyLayout::Interleaved => {
+            let mut buffer = Vec::with_capacity(width * elements);
+            let mut rows = channels
+                .iter()
+                .map(|channel| channel.rows_flat())
+                .collect::<Vec<_>>();
+            for _ in 0..height {
+                let current = rows
+                    .iter_mut()
+                    .map(|rows| rows.next().unwrap_or_default())
+                    .collect::<Vec<_>>();
+                buffer.clear();
+                for x in 0..width {
+                    for (channel, row) in channels.iter().zip(&current) {
+                        let pixel_elements = usize::from(channel.pixel_elements.get());
+                        buffer.extend_from_slice(&row[x * pixel_elements..][..pixel_elements]);
+                    }
+                }
+                writer.write_all(as_bytes(&buffer))?;
+            }
+        }
+        NpyLayout::Planar => {
+            let mut buffer = Vec::with_capacity(width);
+            for channel in channels {
+                let pixel_elements = usize::from(channel.pixel_elements.get());
+                for element in 0..pixel_elements {
+                    for row in channel.rows_flat() {
+                        buffer.clear();
+                        buffer.extend(row.iter().skip(element).step_by(pixel_elements).copied());
+                        writer.write_all(as_bytes(&buffer))?;
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn as_bytes<T: PixelTypePrimitive>(values: &[T]) -> &[u8] {
+    // Primitives have no padding
+    unsafe { std::slice::from_raw_parts(values.as_ptr().cast(), size_of_val(values)) }
+}
+
+fn as_bytes_mut<T: PixelTypePrimitive>(values: &mut [T]) -> &mut [u8] {
+    // Primitives have no padding and every bit pattern is valid
+    unsafe { std::slice::from_raw_parts_mut(values.as_mut_ptr().cast(), size_of_val(values)) }
+}
+
+fn write_header(mut writer: impl Write, descr: &str, shape: [usize; 3]) -> std::io::Result<()> {
+    let [a, b, c] = shape;
+    let mut header =
+        format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': ({a}, {b}, {c}), }}");
+    // Magic, version and length are 10 bytes. The data has to start at a multiple of 64
+    let padding = (64 - (MAGIC.len() + 4 + header.len() + 1) % 64) % 64;
+    header.extend(std::iter::repeat_n(' ', padding));
+    header.push('\n');
+    let len = u16::try_from(header.len()).expect("Header with 3 dimensions is short");
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[1, 0])?;
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(header.as_bytes())
+}
+
+struct Header {
+    descr: String,
+    shape: Vec<usize>,
+}
+
+fn read_header(mut reader: impl Read) -> Result<Header, NpyError> {
+    let invalid = |reason| NpyError::InvalidHeader { reason };
+    let mut prefix = [0; 8];
+    reader.read_exact(&mut prefix)?;
+    if &prefix[..6] != MAGIC {
+        return Err(invalid("Missing magic string"));
+    }
+    let len = match prefix[6] {
+        1 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            usize::from(u16::from_le_bytes(len))
+        }
+        2 | 3 => {
+            let mut len = [0; 4];
+            reader.read_exact(&mut len)?;
+            usize::try_from(u32::from_le_bytes(len)).map_err(|_| invalid("Header is too long"))?
+        }
+        _ => return Err(invalid("Unsupported version")),
+    };
+    let mut header = vec![0; len];
+    reader.read_exact(&mut header)?;
+    let header = String::from_utf8(header).map_err(|_| invalid("Header is not UTF-8"))?;
+
+    let descr = dict_value(&header, "descr")
+        .and_then(|value| value.strip_prefix('\''))
+        .and_then(|value| value.split_once('\''))
+        .ok_or(invalid("Missing descr"))?
+        .0;
+    let fortran_order = dict_value(&header, "fortran_order").ok_or(invalid("Missing order"))?;
+    if fortran_order.starts_with("True") {
+        return Err(NpyError::FortranOrder);
+    }
+    let shape = dict_value(&header, "shape")
+        .and_then(|value| value.strip_prefix('('))
+        .and_then(|value| value.split_once(')'))
+        .ok_or(invalid("Missing shape"))?
+        .0
+        .split(',')
+        .map(str::trim)
+        .filter(|x| !x.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid("Invalid shape"))?;
+    Ok(Header {
+        descr: descr.to_owned(),
+        shape,
+    })
+}
+
+/// Trimmed text after `'key':`
+fn dict_value<'a>(header: &'a str, key: &str) -> Option<&'a str> {
+    let (_, rest) = header.split_once(&format!("'{key}'"))?;
+    Some(rest.trim_start().strip_prefix(':')?.trim_start())
+}
+
+fn read_channels<T: PixelTypePrimitive>(
+    reader: &mut dyn Read,
+    shape: &[usize],
+    swap: bool,
+    layout: NpyLayout,
+) -> Result<DynamicImage, NpyError> {
+    let unsupported = || NpyError::UnsupportedShape {
+        shape: shape.to_vec(),
+    };
+    let (planes, height, width, pixel_elements) = match (shape, layout) {
+        (&[height, width], _) => (1, height, width, 1),
+        (&[height, width, elements], NpyLayout::Interleaved) => (1, height, width, elements),
+        (&[planes, height, width], NpyLayout::Planar) => (planes, height, width, 1),
+        _ => return Err(unsupported()),
+    };
+    let dimension = |x: usize| u32::try_from(x).ok().and_then(NonZeroU32::new);
+    let (Some(height), Some(width), Some(pixel_elements)) = (
+        dimension(height),
+        dimension(width),
+        u8::try_from(pixel_elements).ok().and_then(NonZeroU8::new),
+    ) else {
+        return Err(unsupported());
+    };
+    if planes == 0 {
+        return Err(unsupported());
+    }
+    let len = [height.get() as usize, width.get() as usize, planes]
+        .into_iter()
+        .try_fold(usize::from(pixel_elements.get()), usize::checked_mul)
+        .filter(|&len| std::alloc::Layout::array::<T>(len).is_ok())
+        .ok_or_else(unsupported)?;
+
+    // Grows with the read data, so truncated files fail before the whole size is allocated
+    let mut data = Vec::new();
+    while data.len() < len {
+        let start = data.len();
+        let end = len.min(start + READ_CHUNK_BYTES / size_of::<T>());
+        if data.capacity() < end {
+            // Grows exponentially like `Vec`, but not beyond the size of the data
+            data.reserve_exact(len.min(end.max(data.capacity() * 2)) - start);
+        }
+        data.resize(end, T::from_f64(0.0));
+        let bytes = as_bytes_mut(&mut data[start..]);
+        reader.read_exact(bytes)?;
+        if swap {
+            bytes
+                .chunks_exact_mut(size_of::<T>())
+                .for_each(<[u8]>::reverse);
+        }
+    }
+
+    let channels = if planes == 1 {
+        vec![UnsafeImageChannel::new_vec(
+            data,
+            width,
+            height,
+            pixel_elements,
+        )]
+    } else {
+        create_shared_planes(data, &vec![(width, height, pixel_elements); planes])
+            .unwrap_or_else(|_| unreachable!("Data has the size of all planes"))
+    };
+    let mut channels = channels
+        .into_iter()
+        .map(|channel| T::into_runtime_channel(ImageChannel(channel)));
+    let first = channels.next().expect("At least one plane");
+    Ok(DynamicImage::from_channels(first, channels))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::{NpyError, NpyLayout};
+    use crate::{DynamicImage, DynamicImageChannel, Image, ImageChannel};
+
+    fn npy(descr: &str, shape: &str, data: &[u8]) -> Vec<u8> {
+        let header =
+            format!("{{'descr': '{descr}', 'fortran_order': False, 'shape': {shape}, }}\n");
+        let mut file = b"\x93NUMPY\x01\x00".to_vec();
+        file.extend(u16::try_from(header.len()).unwrap().to_le_bytes());
+        file.extend(header.as_bytes());
+        file.extend(data);
+        file
+    }
+
+    #[test]
+    fn interleaved_channel_roundtrip() {
+        let two = NonZeroU32::new(2).unwrap();
+        let channel = ImageChannel::<[u16; 3]>::new_vec(
+            (0..4).map(|x| [x, x + 10, x + 20]).collect(),
+            two,
+            two,
+        );
+
+        let mut file = Vec::new();
+        channel
+            .write_npy(&mut file, NpyLayout::Interleaved)
+            .unwrap();
+        let header_len = usize::from(u16::from_le_bytes([file[8], file[9]]));
+        assert_eq!((10 + header_len) % 64, 0);
+        assert!(String::from_utf8_lossy(&file).contains("'shape': (2, 2, 3)"));
+
+        let image = DynamicImage::read_npy(file.as_slice(), NpyLayout::Interleaved).unwrap();
+        assert_eq!(image.len().get(), 1);
+        assert_eq!(
+            ImageChannel::<[u16; 3]>::try_from(image[0].clone()).unwrap(),
+            channel
+        );
+    }
+
+    #[test]
+    fn planar_image_roundtrip_shares_vec() {
+        let image = Image::<f32, 2>::new_vec(
+            vec![1.0, 2.0, 3.0, 4.0],
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let dynamic = DynamicImage::from(image.clone());
+        let mut file = Vec::new();
+        dynamic.write_npy(&mut file, NpyLayout::Planar).unwrap();
+
+        let mut read = DynamicImage::read_npy(file.as_slice(), NpyLayout::Planar).unwrap();
+        assert_eq!(read, dynamic);
+        let [
+            DynamicImageChannel::F32(first),
+            DynamicImageChannel::F32(second),
+        ] = &mut *read
+        else {
+            panic!("Expected two f32 channels");
+        };
+        assert_eq!(
+            first.buffer_flat().as_ptr().wrapping_add(2),
+            second.buffer_flat().as_ptr()
+        );
+        let ptr = first.buffer_flat().as_ptr();
+        first.primitive_make_mut()[0] = 5.0;
+        assert_eq!(first.buffer_flat().as_ptr(), ptr);
+        let clone = second.clone();
+        second.primitive_make_mut()[0] = 9.0;
+        assert_eq!(clone.buffer_flat(), &[3.0, 4.0]);
+        assert_eq!(second.buffer_flat(), &[9.0, 4.0]);
+    }
+
+    #[test]
+    fn planar_channels_interleaved() {
+        let image = Image::<u8, 3>::new_vec(
+            (0..6).collect(),
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let mut file = Vec::new();
+        DynamicImage::from(image)
+            .write_npy(&mut file, NpyLayout::Interleaved)
+            .unwrap();
+        let read = DynamicImage::read_npy(file.as_slice(), NpyLayout::Interleaved).unwrap();
+        let channel = ImageChannel::<[u8; 3]>::try_from(read[0].clone()).unwrap();
+        assert_eq!(channel.buffer(), &[[0, 2, 4], [1, 3, 5]]);
+    }
+
+    #[test]
+    fn read_foreign_files() {
+        let file = npy(">u2", "(1, 2)", &[0, 1, 1, 0]);
+        let image = DynamicImage::read_npy(file.as_slice(), NpyLayout::Interleaved).unwrap();
+        let channel = ImageChannel::<u16>::try_from(image[0].clone()).unwrap();
+        assert_eq!(channel.buffer(), &[1, 256]);
+
+        let file = npy("<c8", "(1, 1)", &[0; 8]);
+        let error = DynamicImage::read_npy(file.as_slice(), NpyLayout::Interleaved).unwrap_err();
+        assert!(matches!(error, NpyError::UnsupportedDtype { descr } if descr == "<c8"));
+
+        let file = npy("|u1", "(1, 1, 1, 1)", &[0]);
+        let error = DynamicImage::read_npy(file.as_slice(), NpyLayout::Planar).unwrap_err();
+        assert!(matches!(error, NpyError::UnsupportedShape { .. }));
+
+        let file = npy("|u1", "(1, 2)", &[0]);
+        let error = DynamicImage::read_npy(file.as_slice(), NpyLayout::Planar).unwrap_err();
+        assert!(matches!(error, NpyError::Io(_)));
+    }
+
+    #[test]
+    fn reject_huge_shapes() {
+        let file = npy("<f8", "(4294967295, 4294967295, 255)", &[0; 8]);
+        let error = DynamicImage::read_npy(file.as_slice(), NpyLayout::Interleaved).unwrap_err();
+        assert!(matches!(error, NpyError::UnsupportedShape { .. }));
+
+        let file = npy("|u1", "(65536, 65536, 255)", &[0; 8]);
+        let error = DynamicImage::read_npy(file.as_slice(), NpyLayout::Planar).unwrap_err();
+        assert!(matches!(error, NpyError::Io(_)));
+    }
+
+    #[test]
+    fn mixed_primitives_are_rejected() {
+        let image = DynamicImage::from_channels(
+            ImageChannel::<u8>::new_vec(vec![1], NonZeroU32::MIN, NonZeroU32::MIN).into(),
+            [ImageChannel::<f32>::new_vec(vec![1.0], NonZeroU32::MIN, NonZeroU32::MIN).into()],
+        );
+        let error = image.write_npy(Vec::new(), NpyLayout::Planar).unwrap_err();
+        assert!(matches!(error, NpyError::MixedPrimitives));
+    }
+}
@@ -14,6 +14,7 @@ pub struct DynamicSize<T: PixelTypePrimitive>(std::marker::PhantomData<T>);
 impl<T: PixelTypePrimitive> RuntimePixelType for DynamicSize<T> {
     type Primitive = T;
     type PixelSize = RuntimeSize;
+    type WithPrimitive<P: PixelTypePrimitive> = DynamicSize<P>;
 }
 
 pub trait PixelTypePrimitive:
@@ -35,114 +36,64 @@ pub trait PixelTypePrimitive:
     ) -> Option<&mut ImageChannel<DynamicSize<Self>>>;
 }
 
-impl PixelTypePrimitive for u8 {
-    fn into_runtime_channel(i: ImageChannel<DynamicSize<Self>>) -> DynamicImageChannel {
-        DynamicImageChannel::U8(i)
-    }
-
-    fn try_from_dynamic_image(
-        channel: DynamicImageChannel,
-    ) -> Result<ImageChannel<DynamicSize<Self>>, DynamicImageChannel> {
-        if let DynamicImageChannel::U8(channel) = channel {
-            Ok(channel)
-        } else {
-            Err(channel)
-        }
-    }
-
-    fn try_from_dynamic_image_ref(
-        channel: &DynamicImageChannel,
-    ) -> Option<&ImageChannel<DynamicSize<Self>>> {
-        if let DynamicImageChannel::U8(channel) = channel {
-            Some(channel)
-        } else {
-            None
-        }
-    }
-
-    fn try_from_dynamic_image_mut(
-        channel: &mut DynamicImageChannel,
-    ) -> Option<&mut ImageChannel<DynamicSize<Self>>> {
-        if let DynamicImageChannel::U8(channel) = channel {
-            Some(channel)
-        } else {
-            None
-        }
-    }
-}
-impl PixelTypePrimitive for u16 {
-    fn into_runtime_channel(i: ImageChannel<DynamicSize<Self>>) -> DynamicImageChannel {
-        DynamicImageChannel::U16(i)
-    }
-    fn try_from_dynamic_image(
-        channel: DynamicImageChannel,
-    ) -> Result<ImageChannel<DynamicSize<Self>>, DynamicImageChannel> {
-        if let DynamicImageChannel::U16(channel) = channel {
-            Ok(channel)
-        } else {
-            Err(channel)
-        }
-    }
-
-    fn try_from_dynamic_image_ref(
-        channel: &DynamicImageChannel,
-    ) -> Option<&ImageChannel<DynamicSize<Self>>> {
-        if let DynamicImageChannel::U16(channel) = channel {
-            Some(channel)
-        } else {
-            None
-        }
-    }
-
-    fn try_from_dynamic_image_mut(
-        channel: &mut DynamicImageChannel,
-    ) -> Option<&mut ImageChannel<DynamicSize<Self>>> {
-        if let DynamicImageChannel::U16(channel) = channel {
-            Some(channel)
-        } else {
-            None
+macro_rules! impl_pixel_type_primitive {
+    ($primitive:ty, $variant:ident) => {
+        impl PixelTypePrimitive for $primitive {
+            fn into_runtime_channel(i: ImageChannel<DynamicSize<Self>>) -> DynamicImageChannel {
+                DynamicImageChannel::$variant(i)
+            }
+
+            fn try_from_dynamic_image(
+                channel: DynamicImageChannel,
+            ) -> Result<ImageChannel<DynamicSize<Self>>, DynamicImageChannel> {
+                if let DynamicImageChannel::$variant(channel) = channel {
+                    Ok(channel)
+                } else {
+                    Err(channel)
+                }
+            }
+
+            fn try_from_dynamic_image_ref(
+                channel: &DynamicImageChannel,
+            ) -> Option<&ImageChannel<DynamicSize<Self>>> {
+                if let DynamicImageChannel::$variant(channel) = channel {
+                    Some(channel)
+                } else {
+                    None
+                }
+            }
+
+            fn try_from_dynamic_image_mut(
+                channel: &mut DynamicImageChannel,
+            ) -> Option<&mut ImageChannel<DynamicSize<Self>>> {
+                if let DynamicImageChannel::$variant(channel) = channel {
+                    Some(channel)
+                } else {
+                    None
+                }
+            }
         }
-    }
+    };
 }
 
-impl PixelTypePrimitive for f32 {
-    fn into_runtime_channel(i: ImageChannel<DynamicSize<Self>>) -> DynamicImageChannel {
-        DynamicImageChannel::F32(i)
-    }
-    fn try_from_dynamic_image(
-        channel: DynamicImageChannel,
-    ) -> Result<ImageChannel<DynamicSize<Self>>, DynamicImageChannel> {
-        if let DynamicImageChannel::F32(channel) = channel {
-            Ok(channel)
-        } else {
-            Err(channel)
-        }
-    }
-
-    fn try_from_dynamic_image_ref(
-        channel: &DynamicImageChannel,
-    ) -> Option<&ImageChannel<DynamicSize<Self>>> {
-        if let DynamicImageChannel::F32(channel) = channel {
-            Some(channel)
-        } else {
-            None
-        }
-    }
-
-    fn try_from_dynamic_image_mut(
-        channel: &mut DynamicImageChannel,
-    ) -> Option<&mut ImageChannel<DynamicSize<Self>>> {
-        if let DynamicImageChannel::F32(channel) = channel {
-            Some(channel)
-        } else {
-            None
-        }
-    }
-}
+impl_pixel_type_primitive!(u8, U8);
+impl_pixel_type_primitive!(i8, I8);
+impl_pixel_type_primitive!(u16, U16);
+impl_pixel_type_primitive!(i16, I16);
+impl_pixel_type_primitive!(u32, U32);
+impl_pixel_type_primitive!(i32, I32);
+impl_pixel_type_primitive!(f32, F32);
+impl_pixel_type_primitive!(f64, F64);
+#[cfg(feature = "half")]
+impl_pixel_type_primitive!(half::f16, F16);
+#[cfg(feature = "half")]
+impl_pixel_type_primitive!(half::bf16, BF16);
 
 pub trait RuntimePixelType: Clone + Sized + 'static {
     type Primitive: PixelTypePrimitive;
     type PixelSize: PixelSize + Default;
+    /// Pixel with the same number of elements, but a different primitive (e.g. `[u8; 3]` -> `[f32; 3]`)
+    type WithPrimitive<P: PixelTypePrimitive>: RuntimePixelType<Primitive = P, PixelSize = Self::PixelSize>;
 }
 
 pub trait PixelType: RuntimePixelType + Clone + Sized + 'static {
@@ -152,11 +103,13 @@ pub trait PixelType: RuntimePixelType + Clone + Sized + 'static {
 impl<T: PixelTypePrimitive> RuntimePixelType for T {
     type Primitive = T;
     type PixelSize = ComptimeSize<1>;
+    type WithPrimitive<P: PixelTypePrimitive> = P;
 }
 
 impl<T: PixelTypePrimitive, const PIXEL_ELEMENTS: usize> RuntimePixelType for [T; PIXEL_ELEMENTS] {
     type Primitive = T;
     type PixelSize = ComptimeSize<PIXEL_ELEMENTS>;
+    type WithPrimitive<P: PixelTypePrimitive> = [P; PIXEL_ELEMENTS];
 }
 
 impl<T: PixelTypePrimitive> PixelType for T {
@@ -0,0 +1,279 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    num::{NonZeroU8, NonZeroU32},
+    sync::{Arc, Mutex, MutexGuard, PoisonError, Weak},
+};
+
+use crate::{
+    ImageChannel, PixelType, PixelTypePrimitive,
+    channel::{
+        ChannelFactory, ImageChannelAbi, ImageChannelVTable, UnsafeImageChannel,
+        calc_pixel_len_flat, checked_calc_pixel_len_flat,
+    },
+    pixel_elements::PixelSize,
+};
+
+struct PoolFactory;
+
+/// Idle `Vec<T>`s by `TypeId` of `T` and length
+struct IdleBuffers {
+    buffers: HashMap<(TypeId, usize), Vec<Box<dyn Any + Send>>>,
+    max_per_size: usize,
+}
+
+/// Recycles the buffers of dropped channels, to avoid allocations for each frame.
+///
+/// Buffers are keyed by primitive and size. Clones of the pool share the same buffers.
+/// At most [`ChannelPool::DEFAULT_MAX_IDLE`] buffers per key are kept (see [`ChannelPool::with_max_idle`]), further ones are freed.
+/// Buffers which are returned after the pool was dropped are freed.
+#[derive(Clone)]
+pub struct ChannelPool(Arc<Mutex<IdleBuffers>>);
+
+/// Stored in `UnsafeImageChannel::data`
+struct PoolSlot {
+    pool: Weak<Mutex<IdleBuffers>>,
+    /// Checked when the channel is acquired
+    len: usize,
+    capacity: usize,
+}
+
+impl ChannelPool {
+    pub const DEFAULT_MAX_IDLE: usize = 8;
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_max_idle(Self::DEFAULT_MAX_IDLE)
+    }
+
+    /// Keeps at most `max_per_size` idle buffers for each primitive and size
+    #[must_use]
+    pub fn with_max_idle(max_per_size: usize) -> Self {
+        Self(Arc::new(Mutex::new(IdleBuffers {
+            buffers: HashMap::new(),
+            max_per_size,
+        })))
+    }
+
+    /// Hands out a recycled channel, if one with the same primitive and size is available.
+    /// Otherwise, a new zeroed channel is allocated.
+    ///
+    /// The content of recycled channels is unspecified.
+    ///
+    /// # Panics
+    /// Panics if the size overflows `usize`
+    #[must_use]
+    pub fn acquire<TP: PixelType>(&self, width: NonZeroU32, height: NonZeroU32) -> ImageChannel<TP>
+    where
+        TP::Primitive: Default,
+    {
+        let pixel_elements = TP::PixelSize::default().get();
+        ImageChannel::from_unsafe_internal(UnsafeImageChannel::new_pooled(
+            self,
+            width,
+            height,
+            pixel_elements,
+        ))
+    }
+
+    /// Number of buffers which are ready to be recycled
+    #[must_use]
+    pub fn available(&self) -> usize {
+        self.lock().buffers.values().map(Vec::len).sum()
+    }
+
+    /// Frees all buffers which are ready to be recycled
+    pub fn clear(&self) {
+        self.lock().buffers.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, IdleBuffers> {
+        self.0.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn take<T: PixelTypePrimitive + Default>(&self, len: usize) -> Vec<T> {
+        self.lock()
+            .buffers
+            .get_mut(&(TypeId::of::<T>(), len))
+            .and_then(Vec::pop)
+            .and_then(|x| x.downcast::<Vec<T>>().ok())
+            .map_or_else(|| vec![T::default(); len], |x| *x)
+    }
+}
+
+impl Default for ChannelPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ChannelPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChannelPool")
+            .field("available", &self.available())
+            .finish()
+    }
+}
+
+impl<T: PixelTypePrimitive + Default> UnsafeImageChannel<T> {
+    fn new_pooled(
+        pool: &ChannelPool,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Self {
+        let len = checked_calc_pixel_len_flat(width, height, pixel_elements)
+            .expect("size of pooled channel overflows usize");
+        let buffer = pool.take::<T>(len);
+        let slot = PoolSlot {
+            pool: Arc::downgrade(&pool.0),
+            len,
+            capacity: buffer.capacity(),
+        };
+        let ptr = buffer.as_ptr();
+        std::mem::forget(buffer);
+        let vtable = <PoolFactory as ChannelFactory<T>>::VTABLE;
+        unsafe {
+            Self::new_with_vtable(
+                ptr,
+                width,
+                height,
+                calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
+                pixel_elements,
+                vtable,
+                Box::into_raw(Box::new(slot)).cast(),
+            )
+        }
+    }
+}
+
+impl<T: PixelTypePrimitive + Default> ChannelFactory<T> for PoolFactory {
+    const VTABLE: &'static ImageChannelVTable<T> = {
+        unsafe extern "C" fn make_mut<T>(_image: &mut UnsafeImageChannel<T>) {
+            // Do nothing, as the buffer is exclusively owned by the channel
+        }
+        extern "C" fn release_pooled_channel<T: PixelTypePrimitive>(
+            image: &mut UnsafeImageChannel<T>,
+        ) {
+            let slot = unsafe { Box::from_raw(image.data.cast::<PoolSlot>()) };
+            let len = slot.len;
+            let buffer = unsafe { Vec::from_raw_parts(image.ptr.cast_mut(), len, slot.capacity) };
+            if let Some(pool) = slot.pool.upgrade() {
+                let pool = ChannelPool(pool);
+                let mut idle = pool.lock();
+                let max_per_size = idle.max_per_size;
+                let buffers = idle.buffers.entry((TypeId::of::<T>(), len)).or_default();
+                if buffers.len() < max_per_size {
+                    buffers.push(Box::new(buffer));
+                }
+            }
+        }
+        extern "C" fn clone_pooled_channel<T: PixelTypePrimitive + Default>(
+            image: &UnsafeImageChannel<T>,
+        ) -> UnsafeImageChannel<T> {
+            let slot = unsafe { &*image.data.cast::<PoolSlot>() };
+            match slot.pool.upgrade() {
+                Some(pool) => {
+                    let result = UnsafeImageChannel::<T>::new_pooled(
+                        &ChannelPool(pool),
+                        image.width,
+                        image.height,
+                        image.pixel_elements,
+                    );
+                    unsafe { std::slice::from_raw_parts_mut(result.ptr.cast_mut(), slot.len) }
+                        .clone_from_slice(image.buffer_flat());
+                    result
+                }
+                None => crate::arc::clone_slice_into_arc_channel(image),
+            }
+        }
+        &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
+            make_mut,
+            drop: release_pooled_channel,
+            clone: clone_pooled_channel,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::ChannelPool;
+
+    #[test]
+    fn recycle_dropped_channel() {
+        let pool = ChannelPool::new();
+        let two = NonZeroU32::new(2).unwrap();
+        let mut channel = pool.acquire::<[u16; 3]>(two, two);
+        assert_eq!(channel.buffer(), &[[0; 3]; 4]);
+        channel.make_mut()[0] = [1, 2, 3];
+        let ptr = channel.buffer().as_ptr();
+        drop(channel);
+        assert_eq!(pool.available(), 1);
+
+        let channel = pool.acquire::<[u16; 3]>(two, two);
+        assert_eq!(channel.buffer().as_ptr(), ptr);
+        assert_eq!(pool.available(), 0);
+        drop(channel);
+        assert_eq!(pool.available(), 1);
+    }
+
+    #[test]
+    fn key_by_primitive_and_size() {
+        let pool = ChannelPool::new();
+        let two = NonZeroU32::new(2).unwrap();
+        drop(pool.acquire::<u16>(two, two));
+        // Same number of bytes, but different primitive
+        let bytes = pool.acquire::<[u8; 2]>(two, two);
+        assert_eq!(pool.available(), 1);
+        // Same number of primitives with a different layout is recycled
+        let interleaved = pool.acquire::<[u16; 2]>(two, NonZeroU32::MIN);
+        assert_eq!(pool.available(), 0);
+        drop((bytes, interleaved));
+        assert_eq!(pool.available(), 2);
+
+        pool.clear();
+        assert_eq!(pool.available(), 0);
+    }
+
+    #[test]
+    fn limit_idle_buffers_per_size() {
+        let pool = ChannelPool::with_max_idle(2);
+        let channels = [(); 3].map(|()| pool.acquire::<u8>(NonZeroU32::MIN, NonZeroU32::MIN));
+        let other_size = pool.acquire::<u8>(NonZeroU32::new(2).unwrap(), NonZeroU32::MIN);
+        drop((channels, other_size));
+        assert_eq!(pool.available(), 3);
+
+        assert_eq!(ChannelPool::new().0.lock().unwrap().max_per_size, 8);
+    }
+
+    #[test]
+    fn clone_is_pooled() {
+        let pool = ChannelPool::new();
+        let mut channel = pool.acquire::<f32>(NonZeroU32::MIN, NonZeroU32::MIN);
+        channel.make_mut()[0] = 42.0;
+        let clone = channel.clone();
+        assert_eq!(clone.buffer(), &[42.0]);
+        assert_ne!(clone.buffer().as_ptr(), channel.buffer().as_ptr());
+        drop((channel, clone));
+        assert_eq!(pool.available(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflows usize")]
+    fn reject_overflowing_size() {
+        let size = NonZeroU32::new(1 << 31).unwrap();
+        let _ = ChannelPool::new().acquire::<[u8; 4]>(size, size);
+    }
+
+    #[test]
+    fn outlive_pool() {
+        let pool = ChannelPool::new();
+        let channel = pool.acquire::<u8>(NonZeroU32::MIN, NonZeroU32::MIN);
+        drop(pool);
+        let clone = channel.clone();
+        assert_eq!(clone.buffer(), &[0]);
+    }
+}
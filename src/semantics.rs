@@ -0,0 +1,68 @@
+use std::num::NonZeroU8;
+
+/// Meaning of the pixel elements of all channels in order (e.g. `Rgb` for one channel with `[u8; 3]` or three channels with `u8`)
+///
+/// Images carry it in their [`crate::Metadata`]. The crate itself doesn't interpret it, but conversions into other libraries do.
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChannelSemantics {
+    pub layout: ChannelLayout,
+    /// `None`, if it is unknown
+    pub transfer: Option<TransferFunction>,
+}
+
+impl ChannelSemantics {
+    #[must_use]
+    pub const fn new(layout: ChannelLayout) -> Self {
+        Self {
+            layout,
+            transfer: None,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_transfer(self, transfer: TransferFunction) -> Self {
+        Self {
+            transfer: Some(transfer),
+            ..self
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelLayout {
+    Gray,
+    GrayAlpha,
+    Rgb,
+    Bgr,
+    Rgba,
+    Bgra,
+    /// Y, Cb and Cr
+    Yuv,
+    /// CIE L*a*b*
+    Lab,
+}
+
+impl ChannelLayout {
+    /// Number of pixel elements summed over all channels
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub const fn elements(self) -> NonZeroU8 {
+        let elements = match self {
+            ChannelLayout::Gray => 1,
+            ChannelLayout::GrayAlpha => 2,
+            ChannelLayout::Rgb | ChannelLayout::Bgr | ChannelLayout::Yuv | ChannelLayout::Lab => 3,
+            ChannelLayout::Rgba | ChannelLayout::Bgra => 4,
+        };
+        NonZeroU8::new(elements).unwrap()
+    }
+}
+
+/// How the color components (without alpha) are encoded
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransferFunction {
+    Linear,
+    Srgb,
+}
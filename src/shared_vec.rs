@@ -1,34 +1,37 @@
 use std::{
     marker::PhantomData,
-    num::NonZeroU32,
+    num::{NonZeroU8, NonZeroU32},
     sync::atomic::{AtomicUsize, Ordering},
     vec::Vec,
 };
 
 use crate::{
-    ImageChannel, PixelType,
-    channel::{ChannelFactory, ImageChannelVTable, UnsafeImageChannel},
+    ImageChannel, IncompatibleBufferSizeError, PixelType, PixelTypePrimitive,
+    channel::{
+        ChannelFactory, ImageChannelAbi, ImageChannelVTable, UnsafeImageChannel,
+        calc_pixel_len_flat, checked_calc_pixel_len_flat,
+    },
     pixel_elements::PixelSize,
 };
 
 /// Internal structure that holds a Vec (as raw parts) and reference counts
 /// This allows multiple `ImageChannels` to share the same Vec
 #[repr(C)]
-pub struct SharedVecData<T, const CHANNELS: usize> {
+pub struct SharedVecData<T> {
     /// Pointer to the start of the Vec data
     vec: Vec<T>,
     /// Total number of `ImageChannels` using this `SharedVec` (global atomic)
     total_refs: AtomicUsize,
     /// Per-slice reference counts (one per slice/channel) - used to detect if mutual borrowing is ok
-    slice_refs: [AtomicUsize; CHANNELS],
+    slice_refs: Box<[AtomicUsize]>,
 }
 
-impl<T, const CHANNELS: usize> SharedVecData<T, CHANNELS> {
-    fn new(vec: Vec<T>) -> Self {
+impl<T> SharedVecData<T> {
+    fn new(vec: Vec<T>, channels: usize) -> Self {
         Self {
             vec,
-            total_refs: AtomicUsize::new(CHANNELS),
-            slice_refs: std::array::from_fn(|_| AtomicUsize::new(1)),
+            total_refs: AtomicUsize::new(channels),
+            slice_refs: (0..channels).map(|_| AtomicUsize::new(1)).collect(),
         }
     }
 }
@@ -36,16 +39,16 @@ impl<T, const CHANNELS: usize> SharedVecData<T, CHANNELS> {
 /// Metadata stored in a Box, with pointer stored in UnsafeImageChannel.data field
 /// Minimal: only stores what's needed to access the shared data
 #[repr(C)]
-struct SharedVecMetadata<T, const CHANNELS: usize> {
+struct SharedVecMetadata<T> {
     /// Pointer to the `SharedVecData`
-    data_ptr: *mut SharedVecData<T, CHANNELS>,
+    data_ptr: *mut SharedVecData<T>,
     /// Index of this slice (to access the correct `slice_refs` in `SharedVecData`)
     slice_idx: usize,
     /// Start offset in the Vec for this slice
     start: usize,
 }
 
-impl<T, const CHANNELS: usize> Clone for SharedVecMetadata<T, CHANNELS> {
+impl<T> Clone for SharedVecMetadata<T> {
     fn clone(&self) -> Self {
         unsafe {
             let shared = &(*self.data_ptr);
@@ -61,38 +64,34 @@ impl<T, const CHANNELS: usize> Clone for SharedVecMetadata<T, CHANNELS> {
     }
 }
 
-// Single generic extern "C" functions with const CHANNELS
+// Single generic extern "C" functions
 // These are instantiated when added to the vtable
-unsafe extern "C" fn clone_shared_vec<T: 'static, const CHANNELS: usize>(
+unsafe extern "C" fn clone_shared_vec<T: 'static>(
     image: &UnsafeImageChannel<T>,
 ) -> UnsafeImageChannel<T> {
-    let metadata = unsafe { &mut *(image.data.cast::<SharedVecMetadata<T, CHANNELS>>()) };
+    let metadata = unsafe { &mut *(image.data.cast::<SharedVecMetadata<T>>()) };
 
     UnsafeImageChannel {
         ptr: image.ptr,
         width: image.width,
         height: image.height,
+        stride: image.stride,
         vtable: image.vtable,
         data: Box::into_raw(Box::new(metadata.clone())).cast(),
         pixel_elements: image.pixel_elements,
     }
 }
 
-unsafe extern "C" fn make_mut_shared_vec<T: 'static + Clone, const CHANNELS: usize>(
-    image: &mut UnsafeImageChannel<T>,
-) {
-    let metadata = unsafe { &mut *(image.data.cast::<SharedVecMetadata<T, CHANNELS>>()) };
+unsafe extern "C" fn make_mut_shared_vec<T: PixelTypePrimitive>(image: &mut UnsafeImageChannel<T>) {
+    let metadata = unsafe { &mut *(image.data.cast::<SharedVecMetadata<T>>()) };
     let data = metadata.data_ptr;
     let slice_idx = metadata.slice_idx;
 
     let is_unique = unsafe { (*data).slice_refs[slice_idx].load(Ordering::Acquire) == 1 };
 
     if !is_unique {
-        let slice = unsafe {
-            std::slice::from_raw_parts(image.ptr, (image.width.get() * image.height.get()) as usize)
-        };
         *image = UnsafeImageChannel::new_vec(
-            slice.to_vec(),
+            image.to_vec_flat(),
             image.width,
             image.height,
             image.pixel_elements,
@@ -100,11 +99,9 @@ unsafe extern "C" fn make_mut_shared_vec<T: 'static + Clone, const CHANNELS: usi
     }
 }
 
-pub(crate) extern "C" fn drop_shared_vec<T: 'static, const CHANNELS: usize>(
-    image: &mut UnsafeImageChannel<T>,
-) {
+pub(crate) extern "C" fn drop_shared_vec<T: 'static>(image: &mut UnsafeImageChannel<T>) {
     unsafe {
-        let metadata = Box::from_raw(image.data.cast::<SharedVecMetadata<T, CHANNELS>>());
+        let metadata = Box::from_raw(image.data.cast::<SharedVecMetadata<T>>());
         let shared = metadata.data_ptr;
         let slice_idx = metadata.slice_idx;
         let _ = (*shared).slice_refs[slice_idx].fetch_sub(1, Ordering::AcqRel) - 1;
@@ -115,75 +112,192 @@ pub(crate) extern "C" fn drop_shared_vec<T: 'static, const CHANNELS: usize>(
     };
 }
 
-struct SharedVecFactory<T: 'static, const CHANNELS: usize>(PhantomData<(T, [(); CHANNELS])>);
+struct SharedVecFactory<T: 'static>(PhantomData<T>);
 
 // Implement ChannelFactory with const VTABLE using associated const
-// PhantomData makes this type unique for each T and CHANNELS combination
-impl<T: 'static + Clone, const CHANNELS: usize> ChannelFactory<T>
-    for SharedVecFactory<T, CHANNELS>
-{
+// PhantomData makes this type unique for each T
+impl<T: PixelTypePrimitive> ChannelFactory<T> for SharedVecFactory<T> {
     const VTABLE: &'static ImageChannelVTable<T> = {
         &ImageChannelVTable {
-            clone: clone_shared_vec::<T, CHANNELS>,
-            make_mut: make_mut_shared_vec::<T, CHANNELS>,
-            drop: drop_shared_vec::<T, CHANNELS>,
+            abi: ImageChannelAbi::of::<T>(),
+            clone: clone_shared_vec::<T>,
+            make_mut: make_mut_shared_vec::<T>,
+            drop: drop_shared_vec::<T>,
         }
     };
 }
 
-/// Create `ImageChannels` from a Vec, sharing the underlying storage
-/// Note: T: Clone is required for vtable creation, but clone/drop don't actually need it
-pub fn create_shared_channels<TP: PixelType, const CHANNELS: usize>(
-    vec: Vec<TP::Primitive>,
-    sizes: [(NonZeroU32, NonZeroU32); CHANNELS],
-) -> [ImageChannel<TP>; CHANNELS]
-where
-    TP::Primitive: Clone,
-{
-    assert_eq!(
-        vec.len(),
-        sizes.iter().fold(0, |acc, i| acc
-            + i.0.get() as usize
-                * i.1.get() as usize
-                * TP::PixelSize::default().get().get() as usize)
-    );
-    // Create SharedVecData
-    let mut base = vec.as_ptr();
-    let shared_data = Box::new(SharedVecData::<TP::Primitive, CHANNELS>::new(vec));
-    let data_ptr = Box::into_raw(shared_data);
-
-    // Create ImageChannels for each slice
-    std::array::from_fn(|i| {
-        let (width, height) = sizes[i];
-        let start = width.get() as usize
-            * height.get() as usize
-            * TP::PixelSize::default().get().get() as usize;
-
-        let metadata = Box::new(SharedVecMetadata::<TP::Primitive, CHANNELS> {
-            data_ptr,
-            slice_idx: i,
-            start,
-        });
-        let metadata_ptr = Box::into_raw(metadata);
-        let vtable =
-            <SharedVecFactory<TP::Primitive, CHANNELS> as ChannelFactory<TP::Primitive>>::VTABLE;
-
-        let ptr = base;
-
-        unsafe {
-            base = base.add(start);
-            ImageChannel::from_unsafe_internal(UnsafeImageChannel::new_with_vtable(
-                ptr,
-                width,
-                height,
-                TP::PixelSize::default().get(),
-                vtable,
-                metadata_ptr.cast(),
-            ))
+impl<TP: PixelType> ImageChannel<TP> {
+    /// Splits one `Vec` into consecutive channels without copying. Each channel can have its own dimensions (e.g. half-size chroma planes).
+    ///
+    /// The channels are reference counted individually, so [`ImageChannel::make_mut`] only copies, if that channel was cloned.
+    ///
+    /// # Panics
+    /// Panics if the buffer size is not the sum of all channel sizes.
+    #[must_use]
+    pub fn new_shared_vec_flat<const CHANNELS: usize>(
+        input: Vec<TP::Primitive>,
+        dimensions: [(NonZeroU32, NonZeroU32); CHANNELS],
+    ) -> [Self; CHANNELS] {
+        match Self::try_new_shared_vec_flat(input, dimensions) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
         }
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not the sum of all channel sizes.
+    pub fn try_new_shared_vec_flat<const CHANNELS: usize>(
+        input: Vec<TP::Primitive>,
+        dimensions: [(NonZeroU32, NonZeroU32); CHANNELS],
+    ) -> Result<[Self; CHANNELS], IncompatibleBufferSizeError<Vec<TP::Primitive>>> {
+        let pixel_elements = TP::PixelSize::default().get();
+        create_shared_channels(
+            input,
+            dimensions.map(|(width, height)| (width, height, pixel_elements)),
+        )
+        .map(|channels| channels.map(ImageChannel::from_unsafe_internal))
+    }
+}
+
+/// Splits `input` into consecutive channels of (`width`, `height`, `pixel_elements`)
+pub(crate) fn create_shared_channels<T: PixelTypePrimitive, const CHANNELS: usize>(
+    input: Vec<T>,
+    layouts: [(NonZeroU32, NonZeroU32, NonZeroU8); CHANNELS],
+) -> Result<[UnsafeImageChannel<T>; CHANNELS], IncompatibleBufferSizeError<Vec<T>>> {
+    create_shared_planes(input, &layouts).map(|channels| {
+        channels
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("One channel per layout"))
     })
 }
 
+/// Like [`create_shared_channels`] for a number of channels, which is only known at runtime (e.g. planes of a file)
+pub(crate) fn create_shared_planes<T: PixelTypePrimitive>(
+    input: Vec<T>,
+    layouts: &[(NonZeroU32, NonZeroU32, NonZeroU8)],
+) -> Result<Vec<UnsafeImageChannel<T>>, IncompatibleBufferSizeError<Vec<T>>> {
+    let lens = layouts
+        .iter()
+        .map(|&(width, height, pixel_elements)| {
+            checked_calc_pixel_len_flat(width, height, pixel_elements)
+        })
+        .collect::<Vec<_>>();
+    let expected = lens
+        .iter()
+        .try_fold(0usize, |sum, len| sum.checked_add((*len)?));
+    let input = IncompatibleBufferSizeError::check(input, expected, Vec::len)?;
+
+    let mut base = input.as_ptr();
+    let data_ptr = Box::into_raw(Box::new(SharedVecData::new(input, layouts.len())));
+
+    Ok(layouts
+        .iter()
+        .zip(lens)
+        .enumerate()
+        .map(|(i, (&(width, height, pixel_elements), len))| {
+            // Every len fits into the buffer now
+            let len = len.unwrap_or_default();
+            let metadata = Box::new(SharedVecMetadata::<T> {
+                data_ptr,
+                slice_idx: i,
+                start: len,
+            });
+            let vtable = <SharedVecFactory<T> as ChannelFactory<T>>::VTABLE;
+
+            let ptr = base;
+            unsafe {
+                base = base.add(len);
+                UnsafeImageChannel::new_with_vtable(
+                    ptr,
+                    width,
+                    height,
+                    calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
+                    pixel_elements,
+                    vtable,
+                    Box::into_raw(metadata).cast(),
+                )
+            }
+        })
+        .collect())
+}
+
+/// Distance in `T`s between consecutive `channels`, if they are planes of one allocation in ascending order with the same distance
+/// (e.g. images created by [`crate::Image::new_vec`] or [`ImageChannel::new_shared_vec_flat`])
+#[cfg(any(feature = "ndarray", feature = "dlpack"))]
+pub(crate) fn plane_stride<T: crate::PixelTypePrimitive, const CHANNELS: usize>(
+    channels: &[ImageChannel<T>; CHANNELS],
+) -> Option<usize> {
+    let first = &channels[0].0;
+    let plane_stride = if CHANNELS == 1 {
+        first.stride * first.height.get() as usize
+    } else {
+        if !is_same_shared_vec::<T, CHANNELS>(channels.iter().map(|channel| &channel.0)) {
+            return None;
+        }
+        let distance = channels[1].0.ptr.addr().checked_sub(first.ptr.addr())?;
+        distance / size_of::<T>()
+    };
+    let is_evenly_spaced = channels.iter().enumerate().all(|(i, channel)| {
+        channel.0.stride == first.stride
+            && channel.0.ptr.addr() == first.ptr.wrapping_add(i * plane_stride).addr()
+    });
+    is_evenly_spaced.then_some(plane_stride)
+}
+
+/// Whether all `channels` point into the same `Vec` created by [`ImageChannel::new_shared_vec_flat`]
+#[cfg(any(feature = "ndarray", feature = "dlpack"))]
+fn is_same_shared_vec<'a, T: 'static, const CHANNELS: usize>(
+    mut channels: impl Iterator<Item = &'a UnsafeImageChannel<T>>,
+) -> bool {
+    let drop_fn: unsafe extern "C" fn(&mut UnsafeImageChannel<T>) = drop_shared_vec::<T>;
+    let data_ptr = |channel: &UnsafeImageChannel<T>| {
+        std::ptr::fn_addr_eq(channel.vtable.drop, drop_fn)
+            .then(|| unsafe { (*channel.data.cast::<SharedVecMetadata<T>>()).data_ptr })
+    };
+    let Some(first) = channels.next().and_then(data_ptr) else {
+        return false;
+    };
+    if unsafe { &*first }.slice_refs.len() != CHANNELS {
+        return false;
+    }
+    channels.all(|channel| data_ptr(channel) == Some(first))
+}
+
+/// Returns the original `Vec`, if `channels` are all channels created by [`ImageChannel::new_shared_vec_flat`] in their original order and none of them is shared
+pub(crate) fn try_reclaim_vec<TP: PixelType, const CHANNELS: usize>(
+    channels: [ImageChannel<TP>; CHANNELS],
+) -> Result<Vec<TP::Primitive>, [ImageChannel<TP>; CHANNELS]> {
+    let drop_fn: unsafe extern "C" fn(&mut UnsafeImageChannel<TP::Primitive>) =
+        drop_shared_vec::<TP::Primitive>;
+    let Some(first) = channels.first() else {
+        return Err(channels);
+    };
+    if !std::ptr::fn_addr_eq(first.0.vtable.drop, drop_fn) {
+        return Err(channels);
+    }
+    let data_ptr = unsafe { (*first.0.data.cast::<SharedVecMetadata<TP::Primitive>>()).data_ptr };
+    let is_reclaimable = channels.iter().enumerate().all(|(i, channel)| {
+        std::ptr::fn_addr_eq(channel.0.vtable.drop, drop_fn) && {
+            let metadata = unsafe { &*channel.0.data.cast::<SharedVecMetadata<TP::Primitive>>() };
+            metadata.data_ptr == data_ptr && metadata.slice_idx == i
+        }
+    }) && {
+        let shared = unsafe { &*data_ptr };
+        shared.slice_refs.len() == CHANNELS && shared.total_refs.load(Ordering::Acquire) == CHANNELS
+    };
+    if !is_reclaimable {
+        return Err(channels);
+    }
+
+    for channel in channels {
+        let channel = std::mem::ManuallyDrop::new(channel);
+        drop(unsafe { Box::from_raw(channel.0.data.cast::<SharedVecMetadata<TP::Primitive>>()) });
+    }
+    let shared = unsafe { Box::from_raw(data_ptr) };
+    Ok(shared.vec)
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -195,7 +309,7 @@ mod tests {
         let orig_ptr = vec.as_ptr();
         let width = NonZeroU32::new(2).unwrap();
         let height = NonZeroU32::new(1).unwrap();
-        let mut channels = create_shared_channels::<u8, 3>(vec, [(width, height); 3]);
+        let mut channels = ImageChannel::<u8>::new_shared_vec_flat(vec, [(width, height); 3]);
         let mutbuf = channels[0].make_mut();
         assert_eq!(mutbuf.as_ptr(), orig_ptr);
     }
@@ -206,7 +320,7 @@ mod tests {
         let orig_ptr = vec.as_ptr();
         let width = NonZeroU32::new(2).unwrap();
         let height = NonZeroU32::new(1).unwrap();
-        let mut channels = create_shared_channels::<u8, 3>(vec, [(width, height); 3]);
+        let mut channels = ImageChannel::<u8>::new_shared_vec_flat(vec, [(width, height); 3]);
         let clone = channels[0].clone();
         let mutbuf = channels[0].make_mut();
         assert_eq!(clone.buffer().as_ptr(), orig_ptr);
@@ -219,7 +333,7 @@ mod tests {
         let orig_ptr = vec.as_ptr();
         let width = NonZeroU32::new(2).unwrap();
         let height = NonZeroU32::new(1).unwrap();
-        let mut channels = create_shared_channels::<u8, 3>(vec, [(width, height); 3]);
+        let mut channels = ImageChannel::<u8>::new_shared_vec_flat(vec, [(width, height); 3]);
         drop(channels[0].clone());
         let mutbuf = channels[0].make_mut();
         assert_eq!(mutbuf.as_ptr(), orig_ptr);
@@ -235,7 +349,7 @@ mod tests {
         let height = NonZeroU32::new(1).unwrap();
         let len_per_channel = 2;
 
-        let channels = create_shared_channels::<u8, 3>(vec, [(width, height); 3]);
+        let channels = ImageChannel::<u8>::new_shared_vec_flat(vec, [(width, height); 3]);
 
         // Verify that channels point to the correct offsets in the original Vec
         assert_eq!(
@@ -261,7 +375,7 @@ mod tests {
         let width = NonZeroU32::new(2).unwrap();
         let height = NonZeroU32::new(1).unwrap();
 
-        let channels = create_shared_channels::<u8, 2>(vec, [(width, height); 2]);
+        let channels = ImageChannel::<u8>::new_shared_vec_flat(vec, [(width, height); 2]);
         let channel1_clone = channels[0].clone();
 
         assert_eq!(
@@ -269,4 +383,62 @@ mod tests {
             channel1_clone.buffer().as_ptr()
         );
     }
+
+    #[test]
+    fn different_dimensions() {
+        let vec = (0u8..24).collect::<Vec<_>>();
+        let orig_ptr = vec.as_ptr();
+        let four = NonZeroU32::new(4).unwrap();
+        let two = NonZeroU32::new(2).unwrap();
+        let [y, u, v] =
+            ImageChannel::<u8>::new_shared_vec_flat(vec, [(four, four), (two, two), (two, two)]);
+        assert_eq!(y.dimensions(), (four, four));
+        assert_eq!(u.buffer(), &[16, 17, 18, 19]);
+        assert_eq!(v.buffer(), &[20, 21, 22, 23]);
+        assert_eq!(v.buffer().as_ptr(), unsafe { orig_ptr.add(20) });
+    }
+
+    #[test]
+    fn make_mut_copies_all_pixel_elements() {
+        let [mut a, b] = ImageChannel::<[u8; 2]>::new_shared_vec_flat(
+            vec![1, 2, 3, 4],
+            [(NonZeroU32::MIN, NonZeroU32::MIN); 2],
+        );
+        let clone = a.clone();
+        a.make_mut()[0][1] = 5;
+        assert_eq!(a.buffer(), &[[1, 5]]);
+        assert_eq!(clone.buffer(), &[[1, 2]]);
+        assert_eq!(b.buffer(), &[[3, 4]]);
+    }
+
+    #[test]
+    fn reject_incompatible_size() {
+        let err = ImageChannel::<u8>::try_new_shared_vec_flat(
+            vec![1, 2, 3],
+            [(NonZeroU32::MIN, NonZeroU32::MIN); 2],
+        )
+        .unwrap_err();
+        assert_eq!((err.buffer, err.expected), (vec![1, 2, 3], Some(2)));
+    }
+
+    #[test]
+    fn reclaim_unique_channels() {
+        let vec = vec![0u8, 1, 2, 3, 4];
+        let orig_ptr = vec.as_ptr();
+        let two = NonZeroU32::new(2).unwrap();
+        let mut channels = ImageChannel::<u8>::new_shared_vec_flat(
+            vec,
+            [(two, two), (NonZeroU32::MIN, NonZeroU32::MIN)],
+        );
+        channels[1].make_mut()[0] = 42;
+        let clone = channels[0].clone();
+        let channels = try_reclaim_vec(channels).unwrap_err();
+        drop(clone);
+        let [a, b] = channels;
+        // Wrong order
+        let [b, a] = try_reclaim_vec([b, a]).unwrap_err();
+        let vec = try_reclaim_vec([a, b]).unwrap();
+        assert_eq!(vec, [0, 1, 2, 3, 42]);
+        assert_eq!(vec.as_ptr(), orig_ptr);
+    }
 }
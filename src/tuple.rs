@@ -0,0 +1,247 @@
+//! Images, whose channels have different pixel types (e.g. `ImageChannels<(ImageChannel<[u8; 3]>, ImageChannel<f32>)>` for RGB and depth)
+use std::fmt::{self, Debug, Formatter};
+
+use crate::{
+    DynamicImage, DynamicImageChannel, ImageChannel, ImageChannels, IncompatibleImageError,
+    IncompatibleImageErrorReason, Metadata, PixelType, channel::BorrowableImageChannel,
+};
+
+macro_rules! impl_tuple_image {
+    ($($index:tt: $name:ident),+) => {
+        impl<$($name: BorrowableImageChannel),+> ImageChannels<($($name,)+)> {
+            #[must_use]
+            pub fn width(&self) -> std::num::NonZeroU32 {
+                // All channels have the same dimensions (validated at construction)
+                self.0.0.borrow().width()
+            }
+
+            #[must_use]
+            pub fn height(&self) -> std::num::NonZeroU32 {
+                self.0.0.borrow().height()
+            }
+
+            #[must_use]
+            pub fn dimensions(&self) -> (std::num::NonZeroU32, std::num::NonZeroU32) {
+                self.0.0.borrow().dimensions()
+            }
+
+            #[must_use]
+            pub fn channels(&self) -> ($(&ImageChannel<$name::Pixel>,)+) {
+                ($(self.0.$index.borrow(),)+)
+            }
+
+            #[must_use]
+            pub fn buffers(&self) -> ($(&[$name::Pixel],)+) {
+                ($(self.0.$index.borrow().buffer(),)+)
+            }
+
+            #[must_use]
+            pub fn into_channels(self) -> ($($name,)+) {
+                self.0
+            }
+        }
+
+        impl<$($name: BorrowableImageChannel),+> TryFrom<($($name,)+)>
+            for ImageChannels<($($name,)+)>
+        {
+            type Error = IncompatibleImageError<($($name,)+)>;
+
+            fn try_from(channels: ($($name,)+)) -> Result<Self, Self::Error> {
+                let dimensions = [$(channels.$index.borrow().dimensions()),+];
+                if let Some(b) = dimensions.iter().find(|b| dimensions[0] != **b) {
+                    let (a, b) = (dimensions[0], *b);
+                    return Err(IncompatibleImageError {
+                        image: channels,
+                        reason: IncompatibleImageErrorReason::MixedImageSizes { a, b },
+                    });
+                }
+                Ok(Self(channels, Metadata::new()))
+            }
+        }
+
+        impl<$($name: BorrowableImageChannel),+> PartialEq for ImageChannels<($($name,)+)> {
+            fn eq(&self, other: &Self) -> bool {
+                $(self.0.$index.borrow() == other.0.$index.borrow())&&+
+            }
+        }
+
+        impl<$($name: BorrowableImageChannel),+> Debug for ImageChannels<($($name,)+)> {
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.debug_struct("Image")
+                    .field("width", &self.width())
+                    .field("height", &self.height())
+                    .field("pixels", &[$(std::any::type_name::<$name::Pixel>()),+])
+                    .finish()
+            }
+        }
+
+        impl<'a, $($name: PixelType),+> TryFrom<&'a DynamicImage>
+            for ImageChannels<($(&'a ImageChannel<$name>,)+)>
+        {
+            type Error = IncompatibleImageError<&'a DynamicImage>;
+
+            fn try_from(value: &'a DynamicImage) -> Result<Self, Self::Error> {
+                const CHANNELS: usize = [$($index),+].len();
+                if value.len().get() < CHANNELS {
+                    return Err(IncompatibleImageError {
+                        image: value,
+                        reason: IncompatibleImageErrorReason::RequiresMoreChannels {
+                            expected: const { crate::unwrap_usize_to_nonzero_u8(CHANNELS) },
+                            actual: crate::unwrap_usize_to_nonzero_u8(value.len().get()),
+                        },
+                    });
+                }
+                let channels = ($(
+                    match <&ImageChannel<$name>>::try_from(&value[$index]) {
+                        Ok(typed) => typed,
+                        Err(()) => {
+                            return Err(IncompatibleImageError {
+                                image: value,
+                                reason: IncompatibleImageErrorReason::incompatible_pixel_type::<
+                                    $name,
+                                >($index, &value[$index]),
+                            });
+                        }
+                    },
+                )+);
+                match Self::try_from(channels) {
+                    Ok(image) => Ok(ImageChannels(image.0, value.metadata().clone())),
+                    Err(e) => Err(IncompatibleImageError {
+                        image: value,
+                        reason: e.reason,
+                    }),
+                }
+            }
+        }
+
+        impl<$($name: PixelType),+> TryFrom<DynamicImage>
+            for ImageChannels<($(ImageChannel<$name>,)+)>
+        {
+            type Error = IncompatibleImageError<DynamicImage>;
+
+            fn try_from(mut value: DynamicImage) -> Result<Self, Self::Error> {
+                // Validated on references first, so `value` is returned unchanged on failure
+                if let Err(e) = ImageChannels::<($(&ImageChannel<$name>,)+)>::try_from(&value) {
+                    let reason = e.reason;
+                    return Err(IncompatibleImageError {
+                        image: value,
+                        reason,
+                    });
+                }
+                let metadata = std::mem::take(value.metadata_mut());
+                let mut channels = value.into_iter();
+                Ok(ImageChannels(
+                    ($(
+                        ImageChannel::<$name>::try_from(channels.next().expect("Checked above"))
+                            .unwrap_or_else(|_| unreachable!("Checked above")),
+                    )+),
+                    metadata,
+                ))
+            }
+        }
+
+        impl<$($name: PixelType),+> ImageChannels<($(ImageChannel<$name>,)+)> {
+            pub fn metadata_mut(&mut self) -> &mut Metadata {
+                &mut self.1
+            }
+        }
+
+        impl<$($name: PixelType),+> From<ImageChannels<($(ImageChannel<$name>,)+)>>
+            for DynamicImage
+        {
+            fn from(
+                ImageChannels(channels, metadata): ImageChannels<($(ImageChannel<$name>,)+)>,
+            ) -> Self {
+                let [first, rest @ ..] = [$(DynamicImageChannel::from(channels.$index)),+];
+                let mut image = DynamicImage::from_channels(first, rest);
+                *image.metadata_mut() = metadata;
+                image
+            }
+        }
+    };
+}
+
+impl_tuple_image!(0: A, 1: B);
+impl_tuple_image!(0: A, 1: B, 2: C);
+impl_tuple_image!(0: A, 1: B, 2: C, 3: D);
+impl_tuple_image!(0: A, 1: B, 2: C, 3: D, 4: E);
+impl_tuple_image!(0: A, 1: B, 2: C, 3: D, 4: E, 5: F);
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use crate::{
+        DynamicImage, ImageChannel, ImageChannels, IncompatibleImageErrorReason, PixelType,
+    };
+
+    type RgbDepth = ImageChannels<(ImageChannel<[u8; 3]>, ImageChannel<f32>)>;
+
+    fn channel<T: PixelType>(data: Vec<T>, width: u32) -> ImageChannel<T> {
+        ImageChannel::new_vec(data, NonZeroU32::new(width).unwrap(), NonZeroU32::MIN)
+    }
+
+    fn rgb_depth() -> RgbDepth {
+        RgbDepth::try_from((
+            channel(vec![[1, 2, 3], [4, 5, 6]], 2),
+            channel(vec![0.5, 1.5], 2),
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn dynamic_roundtrip_keeps_buffers() {
+        let mut image = rgb_depth();
+        image.metadata_mut().insert(42u32);
+        let ptr = image.buffers().1.as_ptr();
+
+        let dynamic = DynamicImage::from(image);
+        assert_eq!(dynamic.len().get(), 2);
+
+        let borrowed: ImageChannels<(&ImageChannel<[u8; 3]>, &ImageChannel<f32>)> =
+            (&dynamic).try_into().unwrap();
+        assert_eq!(
+            borrowed.buffers(),
+            (&[[1, 2, 3], [4, 5, 6]][..], &[0.5, 1.5][..])
+        );
+        assert_eq!(borrowed.metadata().get(), Some(&42u32));
+
+        let image = RgbDepth::try_from(dynamic).unwrap();
+        assert_eq!(image.buffers().1.as_ptr(), ptr);
+        assert_eq!(image.metadata().get(), Some(&42u32));
+        assert_eq!(image, rgb_depth());
+    }
+
+    #[test]
+    fn reject_mixed_sizes() {
+        let error = RgbDepth::try_from((channel(vec![[1, 2, 3]], 1), channel(vec![0.5, 1.5], 2)))
+            .unwrap_err();
+        assert!(matches!(
+            error.reason(),
+            IncompatibleImageErrorReason::MixedImageSizes { .. }
+        ));
+    }
+
+    #[test]
+    fn dynamic_image_is_returned_on_error() {
+        let dynamic = DynamicImage::from(rgb_depth());
+        let error = ImageChannels::<(ImageChannel<[u8; 3]>, ImageChannel<u16>)>::try_from(dynamic)
+            .unwrap_err();
+        assert!(matches!(
+            error.reason(),
+            IncompatibleImageErrorReason::IncompatiblePixelType { channel: 1, .. }
+        ));
+        assert_eq!(error.image, DynamicImage::from(rgb_depth()));
+
+        let error = ImageChannels::<(
+            ImageChannel<[u8; 3]>,
+            ImageChannel<f32>,
+            ImageChannel<f32>,
+        )>::try_from(error.image)
+        .unwrap_err();
+        assert!(matches!(
+            error.reason(),
+            IncompatibleImageErrorReason::RequiresMoreChannels { .. }
+        ));
+    }
+}
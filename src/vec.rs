@@ -1,10 +1,16 @@
 use std::num::{NonZeroU8, NonZeroU32};
 
-use crate::channel::{ChannelFactory, ImageChannelVTable, UnsafeImageChannel, calc_pixel_len_flat};
+use crate::{
+    PixelTypePrimitive,
+    channel::{
+        ChannelFactory, ImageChannelAbi, ImageChannelVTable, IncompatibleBufferSizeError,
+        UnsafeImageChannel, calc_pixel_len_flat, checked_calc_pixel_len_flat,
+    },
+};
 
 struct VecFactory;
 
-impl<T: 'static> UnsafeImageChannel<T> {
+impl<T: PixelTypePrimitive> UnsafeImageChannel<T> {
     #[must_use]
     /// # Panics
     /// Panics if the buffer size is not compatible with the width and height.
@@ -13,39 +19,51 @@ impl<T: 'static> UnsafeImageChannel<T> {
         width: NonZeroU32,
         height: NonZeroU32,
         pixel_elements: NonZeroU8,
-    ) -> Self
-    where
-        T: Clone,
-    {
+    ) -> Self {
+        match Self::try_new_vec(input, width, height, pixel_elements) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the `width`, `height` and `pixel_elements`.
+    pub fn try_new_vec(
+        input: Vec<T>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+        pixel_elements: NonZeroU8,
+    ) -> Result<Self, IncompatibleBufferSizeError<Vec<T>>> {
+        let input = IncompatibleBufferSizeError::check(
+            input,
+            checked_calc_pixel_len_flat(width, height, pixel_elements),
+            Vec::len,
+        )?;
         let cap = input.capacity();
         let ptr = input.as_ptr();
-
-        assert_eq!(
-            input.len(),
-            calc_pixel_len_flat(width, height, pixel_elements),
-            "Incompatible Buffer-Size"
-        );
         std::mem::forget(input);
         let vtable = <VecFactory as ChannelFactory<T>>::VTABLE;
-        unsafe {
+        Ok(unsafe {
             Self::new_with_vtable(
                 ptr,
                 width,
                 height,
+                calc_pixel_len_flat(width, NonZeroU32::MIN, pixel_elements),
                 pixel_elements,
                 vtable,
                 std::ptr::without_provenance_mut(cap),
             )
-        }
+        })
     }
 }
 
-impl<T: 'static + Clone> ChannelFactory<T> for VecFactory {
+impl<T: PixelTypePrimitive> ChannelFactory<T> for VecFactory {
     const VTABLE: &'static ImageChannelVTable<T> = {
         unsafe extern "C" fn make_mut<T: Clone>(_image: &mut UnsafeImageChannel<T>) {
             // Do nothing, as ptr is exclusive if i have &mut ImutableVTable
         }
         &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
             make_mut,
             drop: clear_vec_channel,
             clone: crate::arc::clone_slice_into_arc_channel,
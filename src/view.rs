@@ -0,0 +1,391 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    num::NonZeroU32,
+    ops::Deref,
+};
+
+use crate::{
+    ImageChannel, ImageChannels, PixelType, PixelTypePrimitive,
+    channel::{
+        BorrowableImageChannel, ChannelFactory, ImageChannelAbi, ImageChannelVTable,
+        UnsafeImageChannel,
+    },
+    pixel::RuntimePixelType,
+};
+
+struct BorrowedFactory;
+
+impl<T: PixelTypePrimitive> ChannelFactory<T> for BorrowedFactory {
+    const VTABLE: &'static ImageChannelVTable<T> = {
+        unsafe extern "C" fn make_mut<T>(_image: &mut UnsafeImageChannel<T>) {
+            // Do nothing, views are only mutable if the parent was borrowed mutably
+        }
+        unsafe extern "C" fn drop<T>(_image: &mut UnsafeImageChannel<T>) {
+            // Do nothing, the parent owns the buffer
+        }
+        // Clones must not borrow from the parent, so the view is copied into a packed Vec
+        unsafe extern "C" fn clone<T: PixelTypePrimitive>(
+            image: &UnsafeImageChannel<T>,
+        ) -> UnsafeImageChannel<T> {
+            UnsafeImageChannel::new_vec(
+                image.to_vec_flat(),
+                image.width,
+                image.height,
+                image.pixel_elements,
+            )
+        }
+        &ImageChannelVTable {
+            abi: ImageChannelAbi::of::<T>(),
+            clone,
+            make_mut,
+            drop,
+        }
+    };
+}
+
+/// Borrowed rectangular region of a [`ImageChannel`], which shares the buffer of its parent
+///
+/// Derefs to [`ImageChannel`], so all read accessors are available. Rows of a view are usually not contiguous,
+/// so prefer [`ImageChannel::rows`] over [`ImageChannel::buffer`].
+/// Cloning the dereferenced [`ImageChannel`] copies the region into a owned channel.
+pub struct ImageChannelView<'a, TP: RuntimePixelType> {
+    channel: ImageChannel<TP>,
+    _parent: PhantomData<&'a ImageChannel<TP>>,
+}
+
+/// Mutably borrowed rectangular region of a [`ImageChannel`], which shares the buffer of its parent
+///
+/// `&mut ImageChannel` is never handed out, so the view cannot be swapped with a owned channel
+pub struct ImageChannelViewMut<'a, TP: RuntimePixelType> {
+    channel: ImageChannel<TP>,
+    _parent: PhantomData<&'a mut ImageChannel<TP>>,
+}
+
+/// Returns the channel pointing into `parent`, if the region is within its bounds
+fn region<TP: RuntimePixelType>(
+    parent: &ImageChannel<TP>,
+    x: u32,
+    y: u32,
+    width: NonZeroU32,
+    height: NonZeroU32,
+) -> Option<ImageChannel<TP>> {
+    let (parent_width, parent_height) = parent.dimensions();
+    let fits = |offset: u32, len: NonZeroU32, parent_len: NonZeroU32| {
+        offset
+            .checked_add(len.get())
+            .is_some_and(|end| end <= parent_len.get())
+    };
+    if !fits(x, width, parent_width) || !fits(y, height, parent_height) {
+        return None;
+    }
+    let raw = &parent.0;
+    let pixel_elements = raw.pixel_elements;
+    let ptr = unsafe {
+        raw.row_ptr(y)
+            .add(x as usize * pixel_elements.get() as usize)
+    };
+    let vtable = <BorrowedFactory as ChannelFactory<TP::Primitive>>::VTABLE;
+    Some(ImageChannel::from_unsafe_internal(unsafe {
+        UnsafeImageChannel::new_with_vtable(
+            ptr,
+            width,
+            height,
+            raw.stride,
+            pixel_elements,
+            vtable,
+            std::ptr::null_mut(),
+        )
+    }))
+}
+
+impl<TP: RuntimePixelType> ImageChannel<TP> {
+    /// Borrows the region starting at (`x`, `y`) without copying
+    ///
+    /// Returns `None`, if the region exceeds the dimensions of the channel
+    #[must_use]
+    pub fn view(
+        &self,
+        x: u32,
+        y: u32,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Option<ImageChannelView<'_, TP>> {
+        region(self, x, y, width, height).map(|channel| ImageChannelView {
+            channel,
+            _parent: PhantomData,
+        })
+    }
+
+    /// Mutably borrows the region starting at (`x`, `y`) without copying
+    ///
+    /// Shared buffers are copied before (see [`ImageChannel::make_mut`])
+    /// Returns `None`, if the region exceeds the dimensions of the channel
+    pub fn view_mut(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Option<ImageChannelViewMut<'_, TP>> {
+        region(self, x, y, width, height)?;
+        unsafe { (self.0.vtable.make_mut)(&mut self.0) };
+        region(self, x, y, width, height).map(|channel| ImageChannelViewMut {
+            channel,
+            _parent: PhantomData,
+        })
+    }
+}
+
+impl<TP: RuntimePixelType> Deref for ImageChannelView<'_, TP> {
+    type Target = ImageChannel<TP>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.channel
+    }
+}
+
+impl<TP: RuntimePixelType> Clone for ImageChannelView<'_, TP> {
+    fn clone(&self) -> Self {
+        let raw = &self.channel.0;
+        let channel = ImageChannel::from_unsafe_internal(unsafe {
+            UnsafeImageChannel::new_with_vtable(
+                raw.ptr,
+                raw.width,
+                raw.height,
+                raw.stride,
+                raw.pixel_elements,
+                raw.vtable,
+                raw.data,
+            )
+        });
+        Self {
+            channel,
+            _parent: PhantomData,
+        }
+    }
+}
+
+impl<TP: RuntimePixelType> Deref for ImageChannelViewMut<'_, TP> {
+    type Target = ImageChannel<TP>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.channel
+    }
+}
+
+impl<TP: PixelType> ImageChannelViewMut<'_, TP> {
+    /// # Panics
+    /// Panics if the rows are not contiguous in memory. Use [`Self::rows_mut`] instead.
+    pub fn make_mut(&mut self) -> &mut [TP] {
+        self.channel.make_mut()
+    }
+
+    /// Iterates over the rows of the region, skipping the remainder of the parent rows
+    pub fn rows_mut(&mut self) -> impl ExactSizeIterator<Item = &mut [TP]> + '_ {
+        self.channel.rows_mut()
+    }
+}
+
+impl<TP: RuntimePixelType> ImageChannelViewMut<'_, TP> {
+    /// Like [`Self::rows_mut`], but yields the primitives of each row
+    pub fn primitive_rows_mut(
+        &mut self,
+    ) -> impl ExactSizeIterator<Item = &mut [TP::Primitive]> + '_ {
+        self.channel.primitive_rows_mut()
+    }
+}
+
+impl<TP: RuntimePixelType> Debug for ImageChannelView<'_, TP> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.channel.fmt(f)
+    }
+}
+
+impl<TP: RuntimePixelType> Debug for ImageChannelViewMut<'_, TP> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.channel.fmt(f)
+    }
+}
+
+impl<TP: RuntimePixelType> Borrow<ImageChannel<TP>> for ImageChannelView<'_, TP> {
+    fn borrow(&self) -> &ImageChannel<TP> {
+        &self.channel
+    }
+}
+
+impl<TP: RuntimePixelType> Borrow<ImageChannel<TP>> for ImageChannelViewMut<'_, TP> {
+    fn borrow(&self) -> &ImageChannel<TP> {
+        &self.channel
+    }
+}
+
+impl<T: PixelType> BorrowableImageChannel for ImageChannelView<'_, T> {
+    type Pixel = T;
+}
+
+impl<T: PixelType> BorrowableImageChannel for ImageChannelViewMut<'_, T> {
+    type Pixel = T;
+}
+
+impl<T: BorrowableImageChannel, const CHANNELS: usize> ImageChannels<[T; CHANNELS]> {
+    /// Borrows the same region of all channels without copying
+    ///
+    /// Returns `None`, if the region exceeds the dimensions of the image
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn roi(
+        &self,
+        x: u32,
+        y: u32,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Option<ImageChannels<[ImageChannelView<'_, T::Pixel>; CHANNELS]>> {
+        // All channels have the same dimensions, so either all or none are within bounds
+        region(self.0[0].borrow(), x, y, width, height)?;
+
+        let mut channels = self.0.iter();
+        Some(ImageChannels(
+            std::array::from_fn(|_| {
+                channels
+                    .next()
+                    .expect("Array has CHANNELS elements")
+                    .borrow()
+                    .view(x, y, width, height)
+                    .expect("All channels have the same dimensions")
+            }),
+            self.1.clone(),
+        ))
+    }
+}
+
+impl<T: BorrowableImageChannel + BorrowMut<ImageChannel<T::Pixel>>, const CHANNELS: usize>
+    ImageChannels<[T; CHANNELS]>
+{
+    /// Mutably borrows the same region of all channels without copying
+    ///
+    /// Shared buffers are copied before (see [`ImageChannels::make_mut`])
+    /// Returns `None`, if the region exceeds the dimensions of the image
+    #[allow(clippy::missing_panics_doc)]
+    pub fn roi_mut(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Option<ImageChannels<[ImageChannelViewMut<'_, T::Pixel>; CHANNELS]>> {
+        region(self.0[0].borrow(), x, y, width, height)?;
+
+        let mut channels = self.0.iter_mut();
+        Some(ImageChannels(
+            std::array::from_fn(|_| {
+                channels
+                    .next()
+                    .expect("Array has CHANNELS elements")
+                    .borrow_mut()
+                    .view_mut(x, y, width, height)
+                    .expect("All channels have the same dimensions")
+            }),
+            self.1.clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use crate::{Image, ImageChannel};
+
+    const ONE: NonZeroU32 = NonZeroU32::MIN;
+    const TWO: NonZeroU32 = NonZeroU32::new(2).unwrap();
+    const THREE: NonZeroU32 = NonZeroU32::new(3).unwrap();
+
+    #[test]
+    fn miri_view_shares_parent_buffer() {
+        let channel = ImageChannel::<u8>::new_vec((0..9).collect(), THREE, THREE);
+        let view = channel.view(1, 1, TWO, TWO).unwrap();
+        assert_eq!(view.dimensions(), (TWO, TWO));
+        assert!(!view.is_contiguous());
+        assert_eq!(
+            view.rows().collect::<Vec<_>>(),
+            [[4u8, 5].as_slice(), &[7, 8]]
+        );
+        assert_eq!(
+            view.row(0).unwrap().as_ptr(),
+            channel.buffer()[4..].as_ptr()
+        );
+
+        let owned: ImageChannel<u8> = (*view).clone();
+        assert_eq!(
+            owned.buffer(),
+            &[4, 5, 7, 8],
+            "Clone copies into packed buffer"
+        );
+    }
+
+    #[test]
+    fn view_out_of_bounds() {
+        let channel = ImageChannel::<u8>::new_vec((0..9).collect(), THREE, THREE);
+        assert!(channel.view(2, 0, TWO, ONE).is_none());
+        assert!(channel.view(0, 2, ONE, TWO).is_none());
+        assert!(channel.view(u32::MAX, 0, ONE, ONE).is_none());
+        assert!(channel.view(2, 2, ONE, ONE).is_some());
+    }
+
+    #[test]
+    fn miri_view_of_view() {
+        let channel =
+            ImageChannel::<[u8; 2]>::new_vec((0..9).map(|x| [x, x]).collect(), THREE, THREE);
+        let view = channel.view(1, 0, TWO, THREE).unwrap();
+        let inner = view.view(1, 1, ONE, TWO).unwrap();
+        assert_eq!(
+            inner.rows().collect::<Vec<_>>(),
+            [[[5u8, 5]].as_slice(), &[[8, 8]]]
+        );
+    }
+
+    #[test]
+    fn miri_view_mut_writes_into_parent() {
+        let mut channel = ImageChannel::<u8>::new_vec((0..9).collect(), THREE, THREE);
+        let mut view = channel.view_mut(0, 1, TWO, TWO).unwrap();
+        for row in view.rows_mut() {
+            row.fill(0);
+        }
+        assert_eq!(channel.buffer(), &[0, 1, 2, 0, 0, 5, 0, 0, 8]);
+    }
+
+    #[test]
+    fn miri_view_mut_copies_shared_parent() {
+        let mut channel = ImageChannel::<u8>::new_vec((0..4).collect(), TWO, TWO);
+        let clone = channel.clone();
+        channel
+            .view_mut(0, 0, ONE, ONE)
+            .unwrap()
+            .rows_mut()
+            .next()
+            .unwrap()[0] = 42;
+        assert_eq!(channel.buffer(), &[42, 1, 2, 3]);
+        assert_eq!(clone.buffer(), &[0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn miri_image_roi() {
+        let mut image = Image::<u8, 2>::new_vec((0..8).collect(), TWO, TWO);
+        {
+            let roi = image.roi(1, 0, ONE, TWO).unwrap();
+            assert_eq!(roi.dimensions(), (ONE, TWO));
+            let [a, b] = <[_; 2]>::from(roi);
+            assert_eq!(a.rows().collect::<Vec<_>>(), [[1u8].as_slice(), &[3]]);
+            assert_eq!(b.rows().collect::<Vec<_>>(), [[5u8].as_slice(), &[7]]);
+        }
+        assert!(image.roi(1, 1, TWO, ONE).is_none());
+
+        let roi = image.roi_mut(0, 1, TWO, ONE).unwrap();
+        for mut channel in <[_; 2]>::from(roi) {
+            channel.make_mut().fill(0);
+        }
+        assert_eq!(image.buffers(), [[0u8, 1, 0, 0], [4, 5, 0, 0]]);
+    }
+}
@@ -0,0 +1,778 @@
+use std::num::{NonZeroU8, NonZeroU32};
+
+use crate::{
+    DynamicImage, Image, ImageChannel, IncompatibleBufferSizeError, IncompatibleImageError,
+    IncompatibleImageErrorReason, Metadata, PixelType, shared_vec::create_shared_channels,
+    unwrap_usize_to_nonzero_u8,
+};
+
+/// Planar YUV 4:2:0, where the U and V planes have half the width and height (rounded up) of the Y plane.
+///
+/// Conversions from and to RGB use BT.601 in limited range.
+/// The [`Metadata`] is moved along by `From<I420Image>` and `TryFrom<DynamicImage>` and is ignored when images are compared.
+#[derive(Debug, Clone)]
+pub struct I420Image {
+    y: ImageChannel<u8>,
+    u: ImageChannel<u8>,
+    v: ImageChannel<u8>,
+    metadata: Metadata,
+}
+
+/// Metadata is ignored
+impl PartialEq for I420Image {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.y, &self.u, &self.v) == (&other.y, &other.u, &other.v)
+    }
+}
+
+impl I420Image {
+    /// Uses the consecutive Y, U and V planes of `input` without copying
+    ///
+    /// # Panics
+    /// Panics if the buffer size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_vec(input: Vec<u8>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        match Self::try_new_vec(input, width, height) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_vec(
+        input: Vec<u8>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Vec<u8>>> {
+        let (chroma_width, chroma_height) = chroma_dimensions(width, height);
+        let [y, u, v] = create_shared_channels(
+            input,
+            [
+                (width, height, NonZeroU8::MIN),
+                (chroma_width, chroma_height, NonZeroU8::MIN),
+                (chroma_width, chroma_height, NonZeroU8::MIN),
+            ],
+        )?;
+        Ok(Self {
+            y: ImageChannel::from_unsafe_internal(y),
+            u: ImageChannel::from_unsafe_internal(u),
+            v: ImageChannel::from_unsafe_internal(v),
+            metadata: Metadata::new(),
+        })
+    }
+
+    /// # Errors
+    /// Returns the `channels`, if U and V don't have half the dimensions of Y
+    #[allow(clippy::result_large_err)]
+    pub fn try_from_channels(
+        channels: [ImageChannel<u8>; 3],
+    ) -> Result<Self, IncompatibleImageError<[ImageChannel<u8>; 3]>> {
+        let [y, u, v] = &channels;
+        let expected = chroma_dimensions(y.width(), y.height());
+        if let Err(reason) = check_dimensions(1, u.dimensions(), expected)
+            .and_then(|()| check_dimensions(2, v.dimensions(), expected))
+        {
+            return Err(IncompatibleImageError {
+                image: channels,
+                reason,
+            });
+        }
+        let [y, u, v] = channels;
+        Ok(Self {
+            y,
+            u,
+            v,
+            metadata: Metadata::new(),
+        })
+    }
+
+    /// Converts `rgb`, averaging the chroma of each 2x2 block
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn from_rgb(rgb: &Image<[u8; 3], 1>) -> Self {
+        let (width, height) = rgb.dimensions();
+        let (chroma_width, chroma_height) = chroma_dimensions(width, height);
+        let rows = rgb.rows().map(|[row]| row).collect::<Vec<_>>();
+
+        let mut data = rows
+            .iter()
+            .flat_map(|row| row.iter().copied().map(rgb_to_y))
+            .collect::<Vec<_>>();
+        let chroma = subsample(&rows, chroma_width, chroma_height, 2);
+        data.extend(chroma.iter().map(|[u, _]| *u));
+        data.extend(chroma.iter().map(|[_, v]| *v));
+        Self::new_vec(data, width, height)
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_rgb(&self) -> Image<[u8; 3], 1> {
+        let chroma_rows = self.u.rows().zip(self.v.rows());
+        let data = self
+            .y
+            .rows()
+            .zip(chroma_rows.flat_map(|row| [row, row]))
+            .flat_map(|(luma, (u, v))| {
+                luma.iter()
+                    .enumerate()
+                    .map(|(x, luma)| yuv_to_rgb(*luma, u[x / 2], v[x / 2]))
+            })
+            .collect();
+        Image::new_vec(data, self.y.width(), self.y.height())
+    }
+
+    #[must_use]
+    pub fn y(&self) -> &ImageChannel<u8> {
+        &self.y
+    }
+
+    #[must_use]
+    pub fn u(&self) -> &ImageChannel<u8> {
+        &self.u
+    }
+
+    #[must_use]
+    pub fn v(&self) -> &ImageChannel<u8> {
+        &self.v
+    }
+
+    /// Dimensions of the Y plane
+    #[must_use]
+    pub fn dimensions(&self) -> (NonZeroU32, NonZeroU32) {
+        self.y.dimensions()
+    }
+
+    /// Typed values, which are carried along with the pixels (see [`Metadata`])
+    #[must_use]
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    #[must_use]
+    pub fn into_channels(self) -> [ImageChannel<u8>; 3] {
+        [self.y, self.u, self.v]
+    }
+}
+
+impl TryFrom<DynamicImage> for I420Image {
+    type Error = IncompatibleImageError<DynamicImage>;
+
+    fn try_from(mut value: DynamicImage) -> Result<Self, Self::Error> {
+        let check = || {
+            let y = typed_channel::<u8>(&value, 0, 3)?;
+            let expected = chroma_dimensions(y.width(), y.height());
+            for i in 1..3 {
+                check_dimensions(i, typed_channel::<u8>(&value, i, 3)?.dimensions(), expected)?;
+            }
+            Ok(())
+        };
+        if let Err(reason) = check() {
+            return Err(IncompatibleImageError {
+                image: value,
+                reason,
+            });
+        }
+        let metadata = std::mem::take(value.metadata_mut());
+        let mut channels = value.into_iter();
+        Ok(Self {
+            y: into_typed(channels.next()),
+            u: into_typed(channels.next()),
+            v: into_typed(channels.next()),
+            metadata,
+        })
+    }
+}
+
+impl From<I420Image> for DynamicImage {
+    fn from(value: I420Image) -> Self {
+        let mut image =
+            DynamicImage::from_channels(value.y.into(), [value.u.into(), value.v.into()]);
+        *image.metadata_mut() = value.metadata;
+        image
+    }
+}
+
+/// YUV 4:2:0 with a Y plane and one interleaved UV plane, which has half the width and height (rounded up) of the Y plane.
+///
+/// Conversions from and to RGB use BT.601 in limited range.
+/// The [`Metadata`] is moved along by `From<Nv12Image>` and `TryFrom<DynamicImage>` and is ignored when images are compared.
+#[derive(Debug, Clone)]
+pub struct Nv12Image {
+    y: ImageChannel<u8>,
+    uv: ImageChannel<[u8; 2]>,
+    metadata: Metadata,
+}
+
+/// Metadata is ignored
+impl PartialEq for Nv12Image {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.y, &self.uv) == (&other.y, &other.uv)
+    }
+}
+
+impl Nv12Image {
+    /// Uses the consecutive Y and UV planes of `input` without copying
+    ///
+    /// # Panics
+    /// Panics if the buffer size is not compatible with the width and height.
+    #[must_use]
+    pub fn new_vec(input: Vec<u8>, width: NonZeroU32, height: NonZeroU32) -> Self {
+        match Self::try_new_vec(input, width, height) {
+            Ok(x) => x,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    /// # Errors
+    /// Returns the `input`, if the buffer size is not compatible with the width and height.
+    pub fn try_new_vec(
+        input: Vec<u8>,
+        width: NonZeroU32,
+        height: NonZeroU32,
+    ) -> Result<Self, IncompatibleBufferSizeError<Vec<u8>>> {
+        let (chroma_width, chroma_height) = chroma_dimensions(width, height);
+        let [y, uv] = create_shared_channels(
+            input,
+            [
+                (width, height, NonZeroU8::MIN),
+                (chroma_width, chroma_height, <[u8; 2]>::ELEMENTS),
+            ],
+        )?;
+        Ok(Self {
+            y: ImageChannel::from_unsafe_internal(y),
+            uv: ImageChannel::from_unsafe_internal(uv),
+            metadata: Metadata::new(),
+        })
+    }
+
+    /// # Errors
+    /// Returns the channels, if UV doesn't have half the dimensions of Y
+    #[allow(clippy::result_large_err, clippy::type_complexity)]
+    pub fn try_from_channels(
+        y: ImageChannel<u8>,
+        uv: ImageChannel<[u8; 2]>,
+    ) -> Result<Self, IncompatibleImageError<(ImageChannel<u8>, ImageChannel<[u8; 2]>)>> {
+        let expected = chroma_dimensions(y.width(), y.height());
+        match check_dimensions(1, uv.dimensions(), expected) {
+            Ok(()) => Ok(Self {
+                y,
+                uv,
+                metadata: Metadata::new(),
+            }),
+            Err(reason) => Err(IncompatibleImageError {
+                image: (y, uv),
+                reason,
+            }),
+        }
+    }
+
+    /// Converts `rgb`, averaging the chroma of each 2x2 block
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn from_rgb(rgb: &Image<[u8; 3], 1>) -> Self {
+        let (width, height) = rgb.dimensions();
+        let (chroma_width, chroma_height) = chroma_dimensions(width, height);
+        let rows = rgb.rows().map(|[row]| row).collect::<Vec<_>>();
+
+        let mut data = rows
+            .iter()
+            .flat_map(|row| row.iter().copied().map(rgb_to_y))
+            .collect::<Vec<_>>();
+        data.extend(
+            subsample(&rows, chroma_width, chroma_height, 2)
+                .into_iter()
+                .flatten(),
+        );
+        Self::new_vec(data, width, height)
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_rgb(&self) -> Image<[u8; 3], 1> {
+        let data = self
+            .y
+            .rows()
+            .zip(self.uv.rows().flat_map(|row| [row, row]))
+            .flat_map(|(luma, uv)| {
+                luma.iter().enumerate().map(|(x, luma)| {
+                    let [u, v] = uv[x / 2];
+                    yuv_to_rgb(*luma, u, v)
+                })
+            })
+            .collect();
+        Image::new_vec(data, self.y.width(), self.y.height())
+    }
+
+    #[must_use]
+    pub fn y(&self) -> &ImageChannel<u8> {
+        &self.y
+    }
+
+    #[must_use]
+    pub fn uv(&self) -> &ImageChannel<[u8; 2]> {
+        &self.uv
+    }
+
+    /// Dimensions of the Y plane
+    #[must_use]
+    pub fn dimensions(&self) -> (NonZeroU32, NonZeroU32) {
+        self.y.dimensions()
+    }
+
+    /// Typed values, which are carried along with the pixels (see [`Metadata`])
+    #[must_use]
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    #[must_use]
+    pub fn into_channels(self) -> (ImageChannel<u8>, ImageChannel<[u8; 2]>) {
+        (self.y, self.uv)
+    }
+}
+
+impl TryFrom<DynamicImage> for Nv12Image {
+    type Error = IncompatibleImageError<DynamicImage>;
+
+    fn try_from(mut value: DynamicImage) -> Result<Self, Self::Error> {
+        let check = || {
+            let y = typed_channel::<u8>(&value, 0, 2)?;
+            let uv = typed_channel::<[u8; 2]>(&value, 1, 2)?;
+            check_dimensions(1, uv.dimensions(), chroma_dimensions(y.width(), y.height()))
+        };
+        if let Err(reason) = check() {
+            return Err(IncompatibleImageError {
+                image: value,
+                reason,
+            });
+        }
+        let metadata = std::mem::take(value.metadata_mut());
+        let mut channels = value.into_iter();
+        Ok(Self {
+            y: into_typed(channels.next()),
+            uv: into_typed(channels.next()),
+            metadata,
+        })
+    }
+}
+
+impl From<Nv12Image> for DynamicImage {
+    fn from(value: Nv12Image) -> Self {
+        let mut image = DynamicImage::from_channels(value.y.into(), [value.uv.into()]);
+        *image.metadata_mut() = value.metadata;
+        image
+    }
+}
+
+/// Packed YUV 4:2:2, where each pixel stores its luma followed by U (even x) or V (odd x), e.g. `[Y0, U0], [Y1, V0]`.
+/// The width is always even.
+///
+/// Conversions from and to RGB use BT.601 in limited range.
+/// The [`Metadata`] is moved along by `From<YuyvImage>` and `TryFrom<DynamicImage>` and is ignored when images are compared.
+#[derive(Debug, Clone)]
+pub struct YuyvImage(ImageChannel<[u8; 2]>, Metadata);
+
+/// Metadata is ignored
+impl PartialEq for YuyvImage {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl YuyvImage {
+    /// # Errors
+    /// Returns the `channel`, if its width is odd
+    pub fn try_from_channel(
+        channel: ImageChannel<[u8; 2]>,
+    ) -> Result<Self, IncompatibleImageError<ImageChannel<[u8; 2]>>> {
+        match check_even_width(0, channel.width()) {
+            Ok(()) => Ok(Self(channel, Metadata::new())),
+            Err(reason) => Err(IncompatibleImageError {
+                image: channel,
+                reason,
+            }),
+        }
+    }
+
+    /// Converts `rgb`, averaging the chroma of each 2x1 block
+    ///
+    /// # Errors
+    /// Returns the `rgb`, if its width is odd
+    #[allow(clippy::missing_panics_doc)]
+    pub fn from_rgb(
+        rgb: &Image<[u8; 3], 1>,
+    ) -> Result<Self, IncompatibleImageError<&Image<[u8; 3], 1>>> {
+        let (width, height) = rgb.dimensions();
+        if let Err(reason) = check_even_width(0, width) {
+            return Err(IncompatibleImageError { image: rgb, reason });
+        }
+        let rows = rgb.rows().map(|[row]| row).collect::<Vec<_>>();
+        let (chroma_width, _) = chroma_dimensions(width, height);
+        let chroma = subsample(&rows, chroma_width, height, 1);
+        let data = rows
+            .iter()
+            .zip(chroma.chunks_exact(chroma_width.get() as usize))
+            .flat_map(|(row, chroma)| {
+                row.iter()
+                    .enumerate()
+                    .map(|(x, rgb)| [rgb_to_y(*rgb), chroma[x / 2][x % 2]])
+            })
+            .collect();
+        Ok(Self(
+            ImageChannel::new_vec(data, width, height),
+            Metadata::new(),
+        ))
+    }
+
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)]
+    pub fn to_rgb(&self) -> Image<[u8; 3], 1> {
+        let data = self
+            .0
+            .rows()
+            .flat_map(|row| {
+                row.chunks_exact(2).flat_map(|pair| {
+                    let [[y0, u], [y1, v]] = [pair[0], pair[1]];
+                    [yuv_to_rgb(y0, u, v), yuv_to_rgb(y1, u, v)]
+                })
+            })
+            .collect();
+        Image::new_vec(data, self.0.width(), self.0.height())
+    }
+
+    #[must_use]
+    pub fn channel(&self) -> &ImageChannel<[u8; 2]> {
+        &self.0
+    }
+
+    #[must_use]
+    pub fn dimensions(&self) -> (NonZeroU32, NonZeroU32) {
+        self.0.dimensions()
+    }
+
+    /// Typed values, which are carried along with the pixels (see [`Metadata`])
+    #[must_use]
+    pub fn metadata(&self) -> &Metadata {
+        &self.1
+    }
+
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.1
+    }
+
+    #[must_use]
+    pub fn into_channel(self) -> ImageChannel<[u8; 2]> {
+        self.0
+    }
+}
+
+impl TryFrom<DynamicImage> for YuyvImage {
+    type Error = IncompatibleImageError<DynamicImage>;
+
+    fn try_from(mut value: DynamicImage) -> Result<Self, Self::Error> {
+        if let Err(reason) = typed_channel::<[u8; 2]>(&value, 0, 1)
+            .and_then(|channel| check_even_width(0, channel.width()))
+        {
+            return Err(IncompatibleImageError {
+                image: value,
+                reason,
+            });
+        }
+        let metadata = std::mem::take(value.metadata_mut());
+        Ok(Self(into_typed(value.into_iter().next()), metadata))
+    }
+}
+
+impl From<YuyvImage> for DynamicImage {
+    fn from(value: YuyvImage) -> Self {
+        let mut image = DynamicImage::from_channels(value.0.into(), []);
+        *image.metadata_mut() = value.1;
+        image
+    }
+}
+
+fn chroma_dimensions(width: NonZeroU32, height: NonZeroU32) -> (NonZeroU32, NonZeroU32) {
+    let half = |x: NonZeroU32| NonZeroU32::new(x.get().div_ceil(2)).expect("Half of >0 is >0");
+    (half(width), half(height))
+}
+
+fn check_dimensions(
+    channel: usize,
+    actual: (NonZeroU32, NonZeroU32),
+    expected: (NonZeroU32, NonZeroU32),
+) -> Result<(), IncompatibleImageErrorReason> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(IncompatibleImageErrorReason::UnexpectedDimensions {
+            channel,
+            expected,
+            actual,
+        })
+    }
+}
+
+fn check_even_width(channel: usize, width: NonZeroU32) -> Result<(), IncompatibleImageErrorReason> {
+    if width.get().is_multiple_of(2) {
+        Ok(())
+    } else {
+        Err(IncompatibleImageErrorReason::RequiresEvenWidth { channel, width })
+    }
+}
+
+/// Returns the `channel` of `image`, if it has the pixel type `TP`
+fn typed_channel<TP: PixelType>(
+    image: &DynamicImage,
+    channel: usize,
+    expected_channels: usize,
+) -> Result<&ImageChannel<TP>, IncompatibleImageErrorReason> {
+    let dynamic = image
+        .get(channel)
+        .ok_or(IncompatibleImageErrorReason::RequiresMoreChannels {
+            expected: unwrap_usize_to_nonzero_u8(expected_channels),
+            actual: unwrap_usize_to_nonzero_u8(image.len().get()),
+        })?;
+    <&ImageChannel<TP>>::try_from(dynamic)
+        .map_err(|()| IncompatibleImageErrorReason::incompatible_pixel_type::<TP>(channel, dynamic))
+}
+
+/// Only call after [`typed_channel`] succeeded for this channel
+fn into_typed<TP: PixelType>(channel: Option<crate::DynamicImageChannel>) -> ImageChannel<TP> {
+    channel
+        .and_then(|x| ImageChannel::try_from(x).ok())
+        .expect("Channel was checked before")
+}
+
+/// Averages the colors of `block_width` x `block_height` blocks and converts them to `[U, V]`
+fn subsample(
+    rows: &[&[[u8; 3]]],
+    chroma_width: NonZeroU32,
+    chroma_height: NonZeroU32,
+    block_height: usize,
+) -> Vec<[u8; 2]> {
+    const BLOCK_WIDTH: usize = 2;
+    let mut result = Vec::with_capacity(chroma_width.get() as usize * chroma_height.get() as usize);
+    for block_rows in rows.chunks(block_height) {
+        for x in 0..chroma_width.get() as usize {
+            let mut sum = [0u32; 3];
+            let mut count = 0;
+            for row in block_rows {
+                for pixel in row.iter().skip(x * BLOCK_WIDTH).take(BLOCK_WIDTH) {
+                    for (sum, value) in sum.iter_mut().zip(pixel) {
+                        *sum += u32::from(*value);
+                    }
+                    count += 1;
+                }
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            result.push(rgb_to_uv(sum.map(|sum| ((sum + count / 2) / count) as u8)));
+        }
+    }
+    result
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn clamp_u8(value: i32) -> u8 {
+    value.clamp(0, 255) as u8
+}
+
+fn rgb_to_y(rgb: [u8; 3]) -> u8 {
+    let [r, g, b] = rgb.map(i32::from);
+    clamp_u8(((66 * r + 129 * g + 25 * b + 128) >> 8) + 16)
+}
+
+fn rgb_to_uv(rgb: [u8; 3]) -> [u8; 2] {
+    let [r, g, b] = rgb.map(i32::from);
+    [
+        clamp_u8(((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128),
+        clamp_u8(((112 * r - 94 * g - 18 * b + 128) >> 8) + 128),
+    ]
+}
+
+fn yuv_to_rgb(luma: u8, cb: u8, cr: u8) -> [u8; 3] {
+    let luma = 298 * (i32::from(luma) - 16) + 128;
+    let cb = i32::from(cb) - 128;
+    let cr = i32::from(cr) - 128;
+    [
+        clamp_u8((luma + 409 * cr) >> 8),
+        clamp_u8((luma - 100 * cb - 208 * cr) >> 8),
+        clamp_u8((luma + 516 * cb) >> 8),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+
+    fn assert_similar(a: &Image<[u8; 3], 1>, b: &Image<[u8; 3], 1>) {
+        assert_eq!(a.dimensions(), b.dimensions());
+        for (a, b) in a.buffer().iter().zip(b.buffer()) {
+            for (a, b) in a.iter().zip(b) {
+                assert!(a.abs_diff(*b) <= 2, "{a} != {b}");
+            }
+        }
+    }
+
+    /// 4x2 image with uniform 2x2 blocks, so subsampling is lossless
+    fn blocks() -> Image<[u8; 3], 1> {
+        let [a, b] = [[200, 30, 60], [10, 120, 240]];
+        Image::new_vec(
+            vec![a, a, b, b, a, a, b, b],
+            NonZeroU32::new(4).unwrap(),
+            NonZeroU32::new(2).unwrap(),
+        )
+    }
+
+    #[test]
+    fn i420_rgb_roundtrip() {
+        let rgb = blocks();
+        let yuv = I420Image::from_rgb(&rgb);
+        assert_eq!(
+            yuv.u().dimensions(),
+            (NonZeroU32::new(2).unwrap(), NonZeroU32::MIN)
+        );
+        assert_similar(&yuv.to_rgb(), &rgb);
+    }
+
+    #[test]
+    fn nv12_rgb_roundtrip() {
+        let rgb = blocks();
+        let yuv = Nv12Image::from_rgb(&rgb);
+        assert_eq!(yuv.uv().len(), 2);
+        assert_similar(&yuv.to_rgb(), &rgb);
+    }
+
+    #[test]
+    fn yuyv_rgb_roundtrip() {
+        let rgb = blocks();
+        let mut yuv = YuyvImage::from_rgb(&rgb).unwrap();
+        assert_similar(&yuv.to_rgb(), &rgb);
+
+        yuv.metadata_mut().insert(7u8);
+        let roundtrip = YuyvImage::try_from(DynamicImage::from(yuv.clone())).unwrap();
+        assert_eq!(roundtrip, yuv);
+        assert_eq!(roundtrip.metadata().get(), Some(&7u8));
+    }
+
+    #[test]
+    fn yuyv_requires_even_width() {
+        let rgb = Image::<[u8; 3], 1>::new_vec(
+            vec![[0; 3]; 3],
+            NonZeroU32::new(3).unwrap(),
+            NonZeroU32::MIN,
+        );
+        let err = YuyvImage::from_rgb(&rgb).unwrap_err();
+        assert_eq!(
+            err.reason(),
+            &IncompatibleImageErrorReason::RequiresEvenWidth {
+                channel: 0,
+                width: NonZeroU32::new(3).unwrap()
+            }
+        );
+    }
+
+    #[test]
+    fn odd_dimensions_round_up_chroma() {
+        let three = NonZeroU32::new(3).unwrap();
+        let rgb = Image::<[u8; 3], 1>::new_vec(vec![[50, 100, 150]; 9], three, three);
+        let yuv = I420Image::from_rgb(&rgb);
+        let two = NonZeroU32::new(2).unwrap();
+        assert_eq!(yuv.v().dimensions(), (two, two));
+        assert_similar(&yuv.to_rgb(), &rgb);
+        assert_similar(&Nv12Image::from_rgb(&rgb).to_rgb(), &rgb);
+    }
+
+    #[test]
+    fn i420_shares_buffer() {
+        let data = (0..6).collect::<Vec<u8>>();
+        let ptr = data.as_ptr();
+        let yuv = I420Image::new_vec(
+            data,
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::new(2).unwrap(),
+        );
+        assert_eq!(yuv.y().buffer().as_ptr(), ptr);
+        assert_eq!(yuv.u().buffer(), &[4]);
+        assert_eq!(yuv.v().buffer(), &[5]);
+
+        let mut yuv = yuv;
+        yuv.metadata_mut().insert(42u32);
+        let dynamic = DynamicImage::from(yuv.clone());
+        assert_eq!(dynamic.metadata().get(), Some(&42u32));
+        let roundtrip = I420Image::try_from(dynamic).unwrap();
+        assert_eq!(roundtrip, yuv);
+        assert_eq!(roundtrip.metadata().get(), Some(&42u32));
+    }
+
+    #[test]
+    fn nv12_dynamic_roundtrip() {
+        let yuv = Nv12Image::new_vec(
+            vec![1, 2, 3, 4, 5, 6],
+            NonZeroU32::new(2).unwrap(),
+            NonZeroU32::new(2).unwrap(),
+        );
+        assert_eq!(yuv.uv().buffer(), &[[5, 6]]);
+        let mut yuv = yuv;
+        yuv.metadata_mut().insert("camera");
+        let dynamic = DynamicImage::from(yuv.clone());
+        let roundtrip = Nv12Image::try_from(dynamic).unwrap();
+        assert_eq!(roundtrip, yuv);
+        assert_eq!(roundtrip.metadata().get(), Some(&"camera"));
+    }
+
+    #[test]
+    fn reject_incompatible_dynamic_images() {
+        let two = NonZeroU32::new(2).unwrap();
+        let planar = DynamicImage::from(Image::<u8, 3>::new_vec(vec![0; 12], two, two));
+        let err = I420Image::try_from(planar).unwrap_err();
+        assert_eq!(
+            err.reason(),
+            &IncompatibleImageErrorReason::UnexpectedDimensions {
+                channel: 1,
+                expected: (NonZeroU32::MIN, NonZeroU32::MIN),
+                actual: (two, two)
+            }
+        );
+        assert_eq!(err.image.len().get(), 3);
+
+        let luma = DynamicImage::from(Image::<u8, 1>::new_vec(vec![0; 4], two, two));
+        let err = Nv12Image::try_from(luma).unwrap_err();
+        assert!(matches!(
+            err.reason(),
+            IncompatibleImageErrorReason::RequiresMoreChannels { .. }
+        ));
+
+        let wrong_type = DynamicImage::from(Image::<u16, 1>::new_vec(vec![0; 4], two, two));
+        let err = YuyvImage::try_from(wrong_type).unwrap_err();
+        assert!(matches!(
+            err.reason(),
+            IncompatibleImageErrorReason::IncompatiblePixelType { channel: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn reject_incompatible_channels() {
+        let two = NonZeroU32::new(2).unwrap();
+        let y = ImageChannel::<u8>::new_vec(vec![0; 4], two, two);
+        let uv = ImageChannel::<[u8; 2]>::new_vec(vec![[0; 2]; 2], two, NonZeroU32::MIN);
+        let err = Nv12Image::try_from_channels(y, uv).unwrap_err();
+        assert!(matches!(
+            err.reason(),
+            IncompatibleImageErrorReason::UnexpectedDimensions { channel: 1, .. }
+        ));
+        let (y, _) = err.image;
+        let chroma = ImageChannel::<u8>::new_vec(vec![0], NonZeroU32::MIN, NonZeroU32::MIN);
+        assert!(I420Image::try_from_channels([y, chroma.clone(), chroma]).is_ok());
+    }
+}
@@ -1,6 +1,6 @@
-use std::num::NonZeroU32;
+use std::num::{NonZeroU8, NonZeroU32};
 
-use imbuf::{DynamicImage, Image, ImageChannel};
+use imbuf::{DynamicImage, Image, ImageChannel, ImageMut, ImageRef, IncompatibleImageErrorReason};
 
 const ONE: NonZeroU32 = NonZeroU32::MIN;
 const TWO: NonZeroU32 = NonZeroU32::new(2).unwrap();
@@ -19,9 +19,32 @@ fn create_from_dynamic_with_different_size() {
     dynamic[0] = ImageChannel::new_vec(vec![42f32, 42.], ONE, TWO).into();
 
     let back = <Image<f32, 3>>::try_from(dynamic.clone()).unwrap_err();
+    assert_eq!(
+        back.reason(),
+        &IncompatibleImageErrorReason::MixedImageSizes {
+            a: (ONE, TWO),
+            b: (ONE, ONE)
+        }
+    );
     assert_eq!(back.image, dynamic);
 }
 
+#[test]
+fn borrow_from_dynamic_with_different_size() {
+    let image: Image<f32, 2> = Image::new_vec(vec![42f32, 42.], ONE, ONE);
+    let mut dynamic = DynamicImage::from(image);
+    dynamic[1] = ImageChannel::new_vec(vec![42f32, 42.], TWO, ONE).into();
+    let expected = IncompatibleImageErrorReason::MixedImageSizes {
+        a: (ONE, ONE),
+        b: (TWO, ONE),
+    };
+
+    let err = <ImageRef<f32, 2>>::try_from(&dynamic).unwrap_err();
+    assert_eq!(err.reason(), &expected);
+    let err = <ImageMut<f32, 2>>::try_from(&mut dynamic).unwrap_err();
+    assert_eq!(err.into_parts().1, expected);
+}
+
 #[test]
 fn create_from_dynamic_with_different_type() {
     let image: Image<f32, 3> = Image::new_vec(vec![42f32, 42., 43.], ONE, ONE);
@@ -29,7 +52,19 @@ fn create_from_dynamic_with_different_type() {
     dynamic[0] = ImageChannel::new_vec(vec![1u16], ONE, ONE).into();
 
     let back = <Image<f32, 3>>::try_from(dynamic.clone()).unwrap_err();
+    let expected = IncompatibleImageErrorReason::IncompatiblePixelType {
+        channel: 0,
+        expected_primitive: "f32",
+        expected_elements: NonZeroU8::MIN,
+        actual_primitive: "u16",
+        actual_elements: NonZeroU8::MIN,
+    };
+    assert_eq!(back.reason(), &expected);
     assert_eq!(back.image, dynamic);
+    assert_eq!(
+        <ImageRef<f32, 3>>::try_from(&dynamic).unwrap_err().reason(),
+        &expected
+    );
 }
 
 #[test]
@@ -37,6 +72,11 @@ fn create_from_dynamic_with_too_few_channels() {
     let image: Image<f32, 3> = Image::new_vec(vec![42f32, 42., 43.], ONE, ONE);
     let dynamic = DynamicImage::from(image);
     let back = <Image<f32, 4>>::try_from(dynamic.clone()).unwrap_err();
+    assert!(matches!(
+        back.reason(),
+        IncompatibleImageErrorReason::RequiresMoreChannels { expected, actual }
+            if expected.get() == 4 && actual.get() == 3
+    ));
     assert_eq!(back.image, dynamic);
 }
 